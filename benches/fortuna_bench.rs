@@ -0,0 +1,231 @@
+//! Benchmarks for generation throughput, pool regeneration and memory footprint.
+//!
+//! Run with `cargo bench --bench fortuna_bench`, results are printed as a table.
+//! Pass `-- --csv` to print them as CSV instead, and `-- <filter>` to only run the benchmarks
+//! whose group contains `<filter>`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use fortuna::{Fortuna, FortunaInline, ReseedPolicy, DEFAULT_POOL_SIZE};
+
+/// Counts the bytes allocated on the heap, to measure the memory footprint.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+fn record_allocation(size: usize) {
+    let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_ALLOCATED.fetch_max(allocated, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// How long every throughput benchmark runs for.
+const MEASUREMENT_TIME: Duration = Duration::from_millis(200);
+/// How often the pool is regenerated per pool size.
+const REGENERATIONS: usize = 20;
+const POOL_SIZES: [usize; 4] = [256, 4_096, DEFAULT_POOL_SIZE, 1 << 20];
+
+struct Measurement {
+    group: &'static str,
+    name: String,
+    value: f64,
+    unit: &'static str,
+}
+
+struct Bench {
+    filter: Option<String>,
+    measurements: Vec<Measurement>,
+}
+
+impl Bench {
+    fn enabled(&self, group: &str) -> bool {
+        self.filter.as_ref().is_none_or(|filter| group.contains(filter.as_str()))
+    }
+
+    fn record(&mut self, group: &'static str, name: impl Into<String>, value: f64, unit: &'static str) {
+        self.measurements.push(Measurement { group, name: name.into(), value, unit });
+    }
+
+    /// Calls `generate` repeatedly for `MEASUREMENT_TIME`, recording the time per call and the
+    /// output in bytes per second.
+    fn throughput<T>(&mut self, name: &str, generate: impl FnMut() -> T) {
+        self.throughput_bytes(name, size_of::<T>(), generate);
+    }
+
+    /// Like `throughput`, for calls generating `bytes_per_call` bytes each.
+    fn throughput_bytes<T>(&mut self, name: &str, bytes_per_call: usize, mut generate: impl FnMut() -> T) {
+        // calls between looking at the clock, fewer for large outputs
+        let batch = ((1 << 16) / bytes_per_call.max(1)).clamp(1, 1_000);
+        // warm up
+        for _ in 0..batch {
+            black_box(generate());
+        }
+        let mut calls: u64 = 0;
+        let start = Instant::now();
+        while start.elapsed() < MEASUREMENT_TIME {
+            for _ in 0..batch {
+                black_box(generate());
+            }
+            calls += batch as u64;
+        }
+        let seconds = start.elapsed().as_secs_f64();
+        let bytes = calls as f64 * bytes_per_call as f64;
+        self.record("throughput", format!("{name} ns/call"), seconds * 1e9 / calls as f64, "ns");
+        self.record("throughput", format!("{name} MiB/s"), bytes / seconds / (1 << 20) as f64, "MiB/s");
+    }
+
+    /// Regenerates the pool of `fortuna` `REGENERATIONS` times, recording the median time spent
+    /// generating a pool of `pool_size` bytes.
+    fn regeneration(&mut self, name: &str, pool_size: usize, mut fortuna: Fortuna) {
+        let elapsed = Arc::new(Mutex::new(Vec::with_capacity(REGENERATIONS)));
+        let recorded = Arc::clone(&elapsed);
+        fortuna.on_regenerate(move |event| recorded.lock().unwrap().push(event.elapsed));
+        // every byte handed out after the first regenerates the pool
+        fortuna.set_reseed_policy(ReseedPolicy::EveryBytes(1));
+        for _ in 0..REGENERATIONS + 1 {
+            black_box(fortuna.random_u8());
+        }
+        let mut elapsed = elapsed.lock().unwrap().clone();
+        assert_eq!(elapsed.len(), REGENERATIONS);
+        elapsed.sort();
+        let median = elapsed[elapsed.len() / 2].as_secs_f64();
+        self.record("regeneration", format!("{name} {pool_size} B"), median * 1e6, "us");
+    }
+
+    /// Records the heap memory `construct` allocates and keeps allocated, plus the size of the
+    /// constructed value itself.
+    fn footprint<T>(&mut self, name: &str, construct: impl FnOnce() -> T) {
+        let before = ALLOCATED.load(Ordering::Relaxed);
+        PEAK_ALLOCATED.store(before, Ordering::Relaxed);
+        let value = black_box(construct());
+        let retained = ALLOCATED.load(Ordering::Relaxed).saturating_sub(before);
+        let peak = PEAK_ALLOCATED.load(Ordering::Relaxed).saturating_sub(before);
+        drop(value);
+        self.record("memory", format!("{name} retained heap"), retained as f64, "B");
+        self.record("memory", format!("{name} peak heap"), peak as f64, "B");
+        self.record("memory", format!("{name} inline size"), size_of::<T>() as f64, "B");
+    }
+
+    fn print_table(&self) {
+        let width = self.measurements.iter().map(|m| m.name.len()).max().unwrap_or(0);
+        let mut group = "";
+        for measurement in &self.measurements {
+            if measurement.group != group {
+                group = measurement.group;
+                println!("\n{group}");
+                println!("{}", "-".repeat(width + 20));
+            }
+            // bytes are counted, everything else is measured
+            let precision = if measurement.unit == "B" { 0 } else { 2 };
+            println!("{:<width$} {:>12.precision$} {}", measurement.name, measurement.value, measurement.unit);
+        }
+    }
+
+    fn print_csv(&self) {
+        println!("group,benchmark,value,unit");
+        for measurement in &self.measurements {
+            println!("{},{},{},{}", measurement.group, measurement.name, measurement.value, measurement.unit);
+        }
+    }
+}
+
+fn main() {
+    let mut csv = false;
+    let mut filter = None;
+    for argument in std::env::args().skip(1) {
+        match argument.as_str() {
+            "--csv" => csv = true,
+            // passed by `cargo bench`
+            "--bench" => {}
+            _ if argument.starts_with('-') => {}
+            _ => filter = Some(argument),
+        }
+    }
+    let mut bench = Bench { filter, measurements: Vec::new() };
+
+    if bench.enabled("throughput") {
+        let mut fortuna = Fortuna::from_seed_u64(42);
+        bench.throughput("random_u8", || fortuna.random_u8());
+        bench.throughput("random_u32", || fortuna.random_u32());
+        bench.throughput("random_u64", || fortuna.random_u64());
+        // what `random_u64` costs if every byte is read on its own
+        bench.throughput("random_u64 byte by byte", || {
+            u64::from_le_bytes(std::array::from_fn(|_| fortuna.random_u8()))
+        });
+        bench.throughput("random_f64", || fortuna.random_f64());
+        bench.throughput("random_bool", || fortuna.random_bool());
+        bench.throughput("random_from_range", || fortuna.random_from_range(0, 1_000));
+        bench.throughput("random_from_f64_range", || fortuna.random_from_f64_range(-1.0, 1.0));
+        bench.throughput("random_index", || fortuna.random_index(1_000));
+        let mut zeroizing = Fortuna::from_seed_u64(42);
+        zeroizing.set_zeroize_consumed(true);
+        bench.throughput("random_u64 zeroizing consumed", || zeroizing.random_u64());
+        let mut buffer = vec![0u8; 1 << 20];
+        bench.throughput_bytes("fill_bytes 1 MiB", buffer.len(), || fortuna.fill_bytes(&mut buffer));
+        let mut unseeded = Fortuna::new();
+        bench.throughput("unseeded random_u64", || unseeded.random_u64());
+        let mut inline = FortunaInline::<4_096>::from_seed_u64(42);
+        bench.throughput("inline random_u64", || inline.random_u64());
+    }
+
+    if bench.enabled("regeneration") {
+        for pool_size in POOL_SIZES {
+            bench.regeneration("seeded", pool_size, Fortuna::create_seeded_size_restricted(vec![42], pool_size));
+            bench.regeneration("unseeded", pool_size, Fortuna::create_size_restricted(pool_size));
+        }
+    }
+
+    if bench.enabled("memory") {
+        bench.footprint("Fortuna::new", Fortuna::new);
+        bench.footprint("Fortuna::new_lazy", Fortuna::new_lazy);
+        bench.footprint("Fortuna::new + release_pool", || {
+            let mut fortuna = Fortuna::new();
+            fortuna.release_pool();
+            fortuna
+        });
+        bench.footprint("Fortuna::from_seed_u64", || Fortuna::from_seed_u64(42));
+        for pool_size in POOL_SIZES {
+            bench.footprint(&format!("Fortuna::create_size_restricted({pool_size})"), || {
+                Fortuna::create_size_restricted(pool_size)
+            });
+        }
+        bench.footprint("FortunaInline::<4096>::from_seed_u64", || FortunaInline::<4_096>::from_seed_u64(42));
+    }
+
+    if csv {
+        bench.print_csv();
+    } else {
+        bench.print_table();
+    }
+}
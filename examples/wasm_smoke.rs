@@ -0,0 +1,43 @@
+//! Exercises Fortuna on `wasm32-unknown-unknown`, run under Node.js by `tests/wasm_test.rs`.
+//!
+//! The host writes its entropy to `host_entropy` before calling `run`. A panic traps, failing
+//! the test.
+
+use fortuna::{Fortuna, FortunaInline};
+
+static mut HOST_ENTROPY: [u8; 32] = [0; 32];
+
+/// Returns where the host writes its entropy to.
+#[no_mangle]
+pub extern "C" fn host_entropy() -> *mut u8 {
+    core::ptr::addr_of_mut!(HOST_ENTROPY) as *mut u8
+}
+
+/// Generates values with every kind of instance, regenerating their pools a few times.
+/// Returns a checksum of all values.
+#[no_mangle]
+pub extern "C" fn run() -> u64 {
+    // SAFETY: the host only writes before calling `run`, and WebAssembly is single threaded here.
+    let entropy = unsafe { *core::ptr::addr_of!(HOST_ENTROPY) };
+    let mut unseeded = Fortuna::create_size_restricted(1_000);
+    let mut injected = Fortuna::from_host_entropy(&entropy);
+    let mut replayable = Fortuna::new_replayable();
+    let mut inline = FortunaInline::<256>::from_entropy_callback(move |buffer| {
+        buffer.copy_from_slice(&entropy[..buffer.len()]);
+    });
+    let mut checksum: u64 = 0;
+    for _ in 0..10_000 {
+        checksum = checksum
+            .wrapping_add(unseeded.random_u64())
+            .wrapping_add(injected.random_u64())
+            .wrapping_add(replayable.random_u64())
+            .wrapping_add(inline.random_u64());
+    }
+    checksum
+}
+
+/// Returns the first `u64` of an instance seeded with `seed`.
+#[no_mangle]
+pub extern "C" fn seeded_u64(seed: u64) -> u64 {
+    Fortuna::from_seed_u64(seed).random_u64()
+}
@@ -0,0 +1,95 @@
+//! The ChaCha20 stream cipher (RFC 8439), used to expand a key into a pool of random bytes.
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+pub mod simd;
+
+use crate::entropy_pool::zeroize::zeroize_words;
+
+/// The length of a ChaCha20 key in bytes.
+pub const KEY_LENGTH: usize = 32;
+
+/// The length of a ChaCha20 block in bytes.
+pub const BLOCK_LENGTH: usize = 64;
+
+const CONSTANTS: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+/// A ChaCha20 keystream generator.
+#[derive(Clone)]
+pub struct ChaCha20 {
+    pub(super) state: [u32; 16],
+}
+
+impl ChaCha20 {
+    /// Creates a keystream generator starting at block `counter`.
+    pub fn new(key: &[u8; KEY_LENGTH], nonce: &[u8; 12], counter: u32) -> Self {
+        let mut state = [0u32; 16];
+        state[..4].copy_from_slice(&CONSTANTS);
+        for (word, chunk) in state[4..12].iter_mut().zip(key.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        state[12] = counter;
+        for (word, chunk) in state[13..].iter_mut().zip(nonce.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Self { state }
+    }
+
+    /// Returns the next block of the keystream.
+    pub fn next_block(&mut self) -> [u8; BLOCK_LENGTH] {
+        let mut working = self.state;
+        for _ in 0..10 {
+            quarter_round(&mut working, 0, 4, 8, 12);
+            quarter_round(&mut working, 1, 5, 9, 13);
+            quarter_round(&mut working, 2, 6, 10, 14);
+            quarter_round(&mut working, 3, 7, 11, 15);
+            quarter_round(&mut working, 0, 5, 10, 15);
+            quarter_round(&mut working, 1, 6, 11, 12);
+            quarter_round(&mut working, 2, 7, 8, 13);
+            quarter_round(&mut working, 3, 4, 9, 14);
+        }
+        let mut block = [0u8; BLOCK_LENGTH];
+        for ((chunk, word), initial) in block.chunks_exact_mut(4).zip(working.iter()).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.wrapping_add(*initial).to_le_bytes());
+        }
+        self.state[12] = self.state[12].wrapping_add(1);
+        block
+    }
+
+    /// Fills `out` with the keystream. A partially used last block is discarded.
+    ///
+    /// Uses the SIMD backends the CPU supports for as many blocks as possible, if there are any
+    /// for the target architecture. The output is the same as with `fill_portable`.
+    pub fn fill(&mut self, out: &mut [u8]) {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+        let written = simd::fill(&mut self.state, out);
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+        let written = 0;
+        self.fill_portable(&mut out[written..]);
+    }
+
+    /// Fills `out` with the keystream one block at a time, without SIMD.
+    pub fn fill_portable(&mut self, out: &mut [u8]) {
+        for chunk in out.chunks_mut(BLOCK_LENGTH) {
+            let block = self.next_block();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+    }
+}
+
+impl Drop for ChaCha20 {
+    fn drop(&mut self) {
+        // the state holds the key
+        zeroize_words(&mut self.state);
+    }
+}
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
@@ -0,0 +1,239 @@
+//! ChaCha20 on 4 (SSE2, NEON) or 8 (AVX2) blocks at once, one block per SIMD lane.
+//!
+//! Every vector holds the same word of consecutive blocks, so the rounds are the scalar rounds
+//! applied to all lanes at once. The output is identical to generating the blocks one by one.
+
+use super::BLOCK_LENGTH;
+
+/// A SIMD implementation of ChaCha20.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx2,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Sse2,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+}
+
+impl Backend {
+    /// Every backend of the target architecture, the fastest first.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub const ALL: &[Backend] = &[Backend::Avx2, Backend::Sse2];
+    #[cfg(target_arch = "aarch64")]
+    pub const ALL: &[Backend] = &[Backend::Neon];
+
+    /// Returns the number of blocks generated at once.
+    #[cfg(all(test, feature = "std"))]
+    pub fn lanes(self) -> usize {
+        match self {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => 8,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse2 => 4,
+            #[cfg(target_arch = "aarch64")]
+            Backend::Neon => 4,
+        }
+    }
+
+    /// Returns if the CPU supports the backend.
+    ///
+    /// Uses the same detection as `get_cpu_features` with the `std` feature. Without it, only the
+    /// features enabled at compile time are used.
+    pub fn is_supported(self) -> bool {
+        match self {
+            #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+            Backend::Avx2 => std::arch::is_x86_feature_detected!("avx2"),
+            #[cfg(all(not(feature = "std"), any(target_arch = "x86", target_arch = "x86_64")))]
+            Backend::Avx2 => cfg!(target_feature = "avx2"),
+            #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+            Backend::Sse2 => std::arch::is_x86_feature_detected!("sse2"),
+            #[cfg(all(not(feature = "std"), any(target_arch = "x86", target_arch = "x86_64")))]
+            Backend::Sse2 => cfg!(target_feature = "sse2"),
+            #[cfg(all(feature = "std", target_arch = "aarch64"))]
+            Backend::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            #[cfg(all(not(feature = "std"), target_arch = "aarch64"))]
+            Backend::Neon => cfg!(target_feature = "neon"),
+        }
+    }
+
+    /// Fills the longest prefix of `out` that is a multiple of `lanes` blocks and advances the
+    /// block counter in `state` accordingly. Returns the number of bytes written, always zero if
+    /// the CPU does not support the backend.
+    pub fn fill(self, state: &mut [u32; 16], out: &mut [u8]) -> usize {
+        if !self.is_supported() {
+            return 0;
+        }
+        // SAFETY: the CPU supports the target features of the backend
+        unsafe {
+            match self {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Backend::Avx2 => x86::fill_avx2(state, out),
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Backend::Sse2 => x86::fill_sse2(state, out),
+                #[cfg(target_arch = "aarch64")]
+                Backend::Neon => aarch64::fill_neon(state, out),
+            }
+        }
+    }
+}
+
+/// Fills as much of `out` as possible with the supported backends, the fastest first.
+/// Returns the number of bytes written, the rest is less than the lanes of any backend.
+pub fn fill(state: &mut [u32; 16], out: &mut [u8]) -> usize {
+    let mut written = 0;
+    for backend in Backend::ALL {
+        written += backend.fill(state, &mut out[written..]);
+    }
+    written
+}
+
+/// Applies the 20 rounds to the vectors `$v`, using the given vector operations.
+macro_rules! rounds {
+    ($v:ident, $add:ident, $xor:ident, $rotl:ident) => {
+        for _ in 0..10 {
+            quarter_round!($v, 0, 4, 8, 12, $add, $xor, $rotl);
+            quarter_round!($v, 1, 5, 9, 13, $add, $xor, $rotl);
+            quarter_round!($v, 2, 6, 10, 14, $add, $xor, $rotl);
+            quarter_round!($v, 3, 7, 11, 15, $add, $xor, $rotl);
+            quarter_round!($v, 0, 5, 10, 15, $add, $xor, $rotl);
+            quarter_round!($v, 1, 6, 11, 12, $add, $xor, $rotl);
+            quarter_round!($v, 2, 7, 8, 13, $add, $xor, $rotl);
+            quarter_round!($v, 3, 4, 9, 14, $add, $xor, $rotl);
+        }
+    };
+}
+
+macro_rules! quarter_round {
+    ($v:ident, $a:literal, $b:literal, $c:literal, $d:literal, $add:ident, $xor:ident, $rotl:ident) => {
+        $v[$a] = $add($v[$a], $v[$b]);
+        $v[$d] = $rotl!($xor($v[$d], $v[$a]), 16);
+        $v[$c] = $add($v[$c], $v[$d]);
+        $v[$b] = $rotl!($xor($v[$b], $v[$c]), 12);
+        $v[$a] = $add($v[$a], $v[$b]);
+        $v[$d] = $rotl!($xor($v[$d], $v[$a]), 8);
+        $v[$c] = $add($v[$c], $v[$d]);
+        $v[$b] = $rotl!($xor($v[$b], $v[$c]), 7);
+    };
+}
+
+/// Writes the blocks held in `words`, `words[i][lane]` being word `i` of block `lane`.
+#[inline(always)]
+fn write_blocks<const LANES: usize>(words: &[[u32; LANES]; 16], out: &mut [u8]) {
+    for (lane, block) in out.chunks_exact_mut(BLOCK_LENGTH).enumerate() {
+        for (word, chunk) in words.iter().zip(block.chunks_exact_mut(4)) {
+            chunk.copy_from_slice(&word[lane].to_le_bytes());
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    use super::{write_blocks, BLOCK_LENGTH};
+
+    macro_rules! rotl_sse2 {
+        ($x:expr, $n:literal) => {{
+            let x = $x;
+            _mm_or_si128(_mm_slli_epi32::<$n>(x), _mm_srli_epi32::<{ 32 - $n }>(x))
+        }};
+    }
+
+    macro_rules! rotl_avx2 {
+        ($x:expr, $n:literal) => {{
+            let x = $x;
+            _mm256_or_si256(_mm256_slli_epi32::<$n>(x), _mm256_srli_epi32::<{ 32 - $n }>(x))
+        }};
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn fill_sse2(state: &mut [u32; 16], out: &mut [u8]) -> usize {
+        const LANES: usize = 4;
+        let mut written = 0;
+        for group in out.chunks_exact_mut(LANES * BLOCK_LENGTH) {
+            let mut initial = [_mm_setzero_si128(); 16];
+            for (vector, word) in initial.iter_mut().zip(state.iter()) {
+                *vector = _mm_set1_epi32(*word as i32);
+            }
+            // every lane is the next block
+            initial[12] = _mm_add_epi32(initial[12], _mm_set_epi32(3, 2, 1, 0));
+            let mut v = initial;
+            rounds!(v, _mm_add_epi32, _mm_xor_si128, rotl_sse2);
+            let mut words = [[0u32; LANES]; 16];
+            for ((word, vector), initial) in words.iter_mut().zip(v.iter()).zip(initial.iter()) {
+                _mm_storeu_si128(word.as_mut_ptr() as *mut __m128i, _mm_add_epi32(*vector, *initial));
+            }
+            write_blocks(&words, group);
+            state[12] = state[12].wrapping_add(LANES as u32);
+            written += group.len();
+        }
+        written
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn fill_avx2(state: &mut [u32; 16], out: &mut [u8]) -> usize {
+        const LANES: usize = 8;
+        let mut written = 0;
+        for group in out.chunks_exact_mut(LANES * BLOCK_LENGTH) {
+            let mut initial = [_mm256_setzero_si256(); 16];
+            for (vector, word) in initial.iter_mut().zip(state.iter()) {
+                *vector = _mm256_set1_epi32(*word as i32);
+            }
+            // every lane is the next block
+            initial[12] = _mm256_add_epi32(initial[12], _mm256_set_epi32(7, 6, 5, 4, 3, 2, 1, 0));
+            let mut v = initial;
+            rounds!(v, _mm256_add_epi32, _mm256_xor_si256, rotl_avx2);
+            let mut words = [[0u32; LANES]; 16];
+            for ((word, vector), initial) in words.iter_mut().zip(v.iter()).zip(initial.iter()) {
+                _mm256_storeu_si256(word.as_mut_ptr() as *mut __m256i, _mm256_add_epi32(*vector, *initial));
+            }
+            write_blocks(&words, group);
+            state[12] = state[12].wrapping_add(LANES as u32);
+            written += group.len();
+        }
+        written
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use core::arch::aarch64::*;
+
+    use super::{write_blocks, BLOCK_LENGTH};
+
+    macro_rules! rotl_neon {
+        ($x:expr, $n:literal) => {{
+            let x = $x;
+            vorrq_u32(vshlq_n_u32::<$n>(x), vshrq_n_u32::<{ 32 - $n }>(x))
+        }};
+    }
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn fill_neon(state: &mut [u32; 16], out: &mut [u8]) -> usize {
+        const LANES: usize = 4;
+        let mut written = 0;
+        let counter_offsets: [u32; LANES] = [0, 1, 2, 3];
+        for group in out.chunks_exact_mut(LANES * BLOCK_LENGTH) {
+            let mut initial = [vdupq_n_u32(0); 16];
+            for (vector, word) in initial.iter_mut().zip(state.iter()) {
+                *vector = vdupq_n_u32(*word);
+            }
+            // every lane is the next block
+            initial[12] = vaddq_u32(initial[12], vld1q_u32(counter_offsets.as_ptr()));
+            let mut v = initial;
+            rounds!(v, vaddq_u32, veorq_u32, rotl_neon);
+            let mut words = [[0u32; LANES]; 16];
+            for ((word, vector), initial) in words.iter_mut().zip(v.iter()).zip(initial.iter()) {
+                vst1q_u32(word.as_mut_ptr(), vaddq_u32(*vector, *initial));
+            }
+            write_blocks(&words, group);
+            state[12] = state[12].wrapping_add(LANES as u32);
+            written += group.len();
+        }
+        written
+    }
+}
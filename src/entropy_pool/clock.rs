@@ -0,0 +1,49 @@
+use core::time::Duration;
+
+/// `true` if the target has a clock to read.
+///
+/// Measuring time needs the standard library. On `wasm32-unknown-unknown` the standard library
+/// exists, but reading the time panics, as there is no host to ask for it.
+pub const CLOCK_AVAILABLE: bool = cfg!(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+));
+
+/// Measures the time elapsed since it was started.
+///
+/// Without a clock (see `CLOCK_AVAILABLE`) no time ever elapses.
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    start: std::time::Instant,
+}
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Self {
+            #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+        return self.start.elapsed();
+        #[cfg(not(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown")))))]
+        return Duration::ZERO;
+    }
+}
+
+#[cfg(feature = "std")]
+/// Returns the time since the unix epoch, or `None` without a clock.
+///
+/// A clock set before 1970 still gives a value, just a less precise one.
+pub fn unix_time() -> Option<Duration> {
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    return Some(match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        Ok(duration) => duration,
+        Err(error) => error.duration(),
+    });
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    return None;
+}
@@ -155,8 +155,8 @@ pub fn get_cpu_features() -> Vec<&'static str> {
 /// Tests for Aarch64 features
 pub fn get_cpu_features() -> Vec<&'static str> {
     let mut features = Vec::new();
-    if std::arch::is_aarch64_feature_detected!("asmid") {
-        features.push("asmid");
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        features.push("neon");
     }
     if std::arch::is_aarch64_feature_detected!("pmull") {
         features.push("pmull");
@@ -440,3 +440,16 @@ pub fn get_cpu_features() -> Vec<&'static str> {
     }
     features
 }
+
+#[cfg(not(any(
+    target_arch = "riscv32",
+    target_arch = "riscv64",
+    target_arch = "aarch64",
+    target_arch = "arm64ec",
+    target_arch = "x86",
+    target_arch = "x86_64"
+)))]
+/// No CPU features can be detected on this architecture
+pub fn get_cpu_features() -> Vec<&'static str> {
+    Vec::new()
+}
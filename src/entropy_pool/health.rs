@@ -0,0 +1,164 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
+/// The probability of a false positive of a single health test, 2^-20 as recommended by
+/// SP 800-90B.
+const FALSE_POSITIVE_PROBABILITY: f64 = 1.0 / (1 << 20) as f64;
+
+#[cfg(feature = "std")]
+/// The window size of the adaptive proportion test for non-binary samples.
+const ADAPTIVE_PROPORTION_WINDOW: usize = 512;
+
+/// The results of the health tests of a single entropy source.
+///
+/// The samples every source collects are tested with the repetition count test and the
+/// adaptive proportion test of SP 800-90B, treating every byte as a sample with the min-entropy
+/// the source claims in `EntropySource::estimated_entropy`. The tests run over everything a
+/// source collected since the pool was created, so the adaptive proportion test sees full windows
+/// of 512 samples even if a source only collects a few bytes per regeneration.
+/// Sources claiming no entropy are not tested. Without the `std` feature no source is tested,
+/// as the tests need floating point math from the standard library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceHealth {
+    /// The name of the source.
+    pub name: String,
+    /// The number of bytes the source collected.
+    pub samples: usize,
+    /// Whether the source was tested at all.
+    pub tested: bool,
+    /// Whether the repetition count test passed, i.e. no byte was repeated too often in a row.
+    pub repetition_count_passed: bool,
+    /// Whether the adaptive proportion test passed, i.e. no byte was too common.
+    pub adaptive_proportion_passed: bool,
+}
+
+impl SourceHealth {
+    /// Returns `true` if all health tests passed, or the source was not tested.
+    pub fn passed(&self) -> bool {
+        self.repetition_count_passed && self.adaptive_proportion_passed
+    }
+}
+
+/// Returned if an entropy source failed its health tests during construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthError {
+    /// The health of every source that failed.
+    pub failed_sources: Vec<SourceHealth>,
+}
+
+impl fmt::Display for HealthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "entropy sources failed health tests:")?;
+        for source in &self.failed_sources {
+            write!(f, " {}", source.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for HealthError {}
+
+/// The state of the health tests of a single source, carried from one collection to the next.
+#[derive(Debug, Clone, Default)]
+// without `std` no source is tested
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub struct HealthState {
+    // The last sample and how often it was repeated in a row.
+    previous: Option<u8>,
+    repetitions: usize,
+    // The first sample of the current window, the samples seen in it and how often the first
+    // sample occurred.
+    window_first: u8,
+    window_samples: usize,
+    window_occurrences: usize,
+}
+
+/// Runs the health tests on the `samples` of the source `name`, continuing from `state`.
+#[cfg_attr(not(feature = "std"), allow(unused_variables))]
+pub fn test_source(name: &str, samples: &[u8], estimated_entropy: u32, state: &mut HealthState) -> SourceHealth {
+    let health = SourceHealth {
+        name: name.to_string(),
+        samples: samples.len(),
+        tested: false,
+        repetition_count_passed: true,
+        adaptive_proportion_passed: true,
+    };
+    if estimated_entropy == 0 || samples.is_empty() {
+        return health;
+    }
+    #[cfg(feature = "std")]
+    let health = {
+        // min-entropy per byte
+        let min_entropy = (estimated_entropy as f64 / samples.len() as f64).min(8.0);
+        SourceHealth {
+            tested: true,
+            repetition_count_passed: state.repetition_count(samples, min_entropy),
+            adaptive_proportion_passed: state.adaptive_proportion(samples, min_entropy),
+            ..health
+        }
+    };
+    health
+}
+
+#[cfg(feature = "std")]
+impl HealthState {
+    /// Fails if any byte is repeated too often in a row for a source with `min_entropy` bits per
+    /// byte, counting the repetitions at the end of earlier samples.
+    pub fn repetition_count(&mut self, samples: &[u8], min_entropy: f64) -> bool {
+        let cutoff = 1 + (-FALSE_POSITIVE_PROBABILITY.log2() / min_entropy).ceil() as usize;
+        let mut passed = true;
+        for &sample in samples {
+            if self.previous == Some(sample) {
+                self.repetitions += 1;
+                passed &= self.repetitions < cutoff;
+            } else {
+                self.previous = Some(sample);
+                self.repetitions = 1;
+            }
+        }
+        passed
+    }
+
+    /// Fails if the first byte of any window occurs too often in that window, for a source with
+    /// `min_entropy` bits per byte. Windows continue from earlier samples.
+    pub fn adaptive_proportion(&mut self, samples: &[u8], min_entropy: f64) -> bool {
+        let probability = 2f64.powf(-min_entropy);
+        let cutoff = 1 + critical_binomial(ADAPTIVE_PROPORTION_WINDOW, probability, 1.0 - FALSE_POSITIVE_PROBABILITY);
+        let mut passed = true;
+        for &sample in samples {
+            if self.window_samples == 0 {
+                self.window_first = sample;
+                self.window_occurrences = 0;
+            }
+            if sample == self.window_first {
+                self.window_occurrences += 1;
+                passed &= self.window_occurrences < cutoff;
+            }
+            self.window_samples = (self.window_samples + 1) % ADAPTIVE_PROPORTION_WINDOW;
+        }
+        passed
+    }
+}
+
+#[cfg(feature = "std")]
+/// Returns the smallest `k` for which the binomial cumulative distribution function of `trials`
+/// and `probability` is at least `quantile`.
+fn critical_binomial(trials: usize, probability: f64, quantile: f64) -> usize {
+    if probability >= 1.0 {
+        return trials;
+    }
+    // in log space, the first terms underflow for large probabilities
+    let log_ratio = (probability / (1.0 - probability)).ln();
+    let mut log_probability = trials as f64 * (1.0 - probability).ln();
+    let mut cumulative = 0.0;
+    for k in 0..trials {
+        cumulative += log_probability.exp();
+        if cumulative >= quantile {
+            return k;
+        }
+        log_probability += ((trials - k) as f64 / (k + 1) as f64).ln() + log_ratio;
+    }
+    trials
+}
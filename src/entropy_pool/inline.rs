@@ -0,0 +1,132 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::entropy_pool::chacha::{ChaCha20, KEY_LENGTH};
+use crate::entropy_pool::generator::fill_entropy_pool;
+use crate::entropy_pool::report::EntropyReport;
+use crate::entropy_pool::sha256::hmac_sha256;
+#[cfg(feature = "std")]
+use crate::entropy_pool::sources::default_sources;
+use crate::entropy_pool::sources::EntropySource;
+use crate::entropy_pool::zeroize::zeroize;
+
+/// Separates the keys of seeded inline pools from the keys of seeded `EntropyPool`s, so the same
+/// seed does not give the same stream.
+const INLINE_SEEDED_SALT: &[u8] = b"fortuna inline seeded pool";
+
+/// `InlinePool` is an entropy pool of `N` pseudo random bytes, stored inline.
+///
+/// Only construction collects entropy and allocates. Every regeneration expands a key into the
+/// pool in place, replacing the key with the first bytes of the keystream, so earlier pools can
+/// not be recovered from the current state.
+pub struct InlinePool<const N: usize> {
+    pool: [u8; N],
+    pool_index: usize,
+    key: [u8; KEY_LENGTH],
+}
+
+impl<const N: usize> InlinePool<N> {
+    /// Creates a new `InlinePool`, with the key generated from the default entropy sources.
+    #[cfg(feature = "std")]
+    pub fn new() -> Self {
+        Self::create_with_sources(default_sources())
+    }
+
+    /// Creates a new `InlinePool`, with the key generated from the given entropy sources.
+    pub fn create_with_sources(mut sources: Vec<Box<dyn EntropySource>>) -> Self {
+        let mut key = [0u8; KEY_LENGTH];
+        fill_entropy_pool(
+            &mut key,
+            &mut sources,
+            &mut Vec::new(),
+            &mut EntropyReport::default(),
+            &mut [0; KEY_LENGTH],
+            None,
+        );
+        let inline_pool = Self::from_key(key);
+        zeroize(&mut key);
+        inline_pool
+    }
+
+    /// Creates a new seeded `InlinePool`.
+    pub fn create_seeded(seed: &[u8]) -> Self {
+        Self::from_key(hmac_sha256(INLINE_SEEDED_SALT, &[seed]))
+    }
+
+    fn from_key(key: [u8; KEY_LENGTH]) -> Self {
+        const { assert!(N > 0, "the pool needs room for at least one byte") };
+        let mut inline_pool = Self {
+            pool: [0; N],
+            pool_index: 0,
+            key,
+        };
+        inline_pool.regenerate_pool();
+        inline_pool
+    }
+
+    /// Returns a byte from the pool.
+    /// If the pool is empty, it will regenerate the pool.
+    pub fn get_random_byte(&mut self) -> u8 {
+        if self.pool_index == N {
+            self.regenerate_pool();
+        }
+        let out = self.pool[self.pool_index];
+        self.pool_index += 1;
+        out
+    }
+
+    /// Returns `L` bytes from the pool, the same bytes `L` calls to `get_random_byte` return.
+    ///
+    /// Bytes within the pool are copied at once, only at the end of the pool they are read one by
+    /// one.
+    pub fn get_random_bytes<const L: usize>(&mut self) -> [u8; L] {
+        let end = self.pool_index + L;
+        if end <= N {
+            let mut out = [0; L];
+            out.copy_from_slice(&self.pool[self.pool_index..end]);
+            self.pool_index = end;
+            out
+        } else {
+            core::array::from_fn(|_| self.get_random_byte())
+        }
+    }
+
+    /// Fills `dest` with bytes from the pool, the same bytes `dest.len()` calls to
+    /// `get_random_byte` return.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.pool_index == N {
+                self.regenerate_pool();
+            }
+            let length = (N - self.pool_index).min(dest.len() - filled);
+            dest[filled..filled + length].copy_from_slice(&self.pool[self.pool_index..self.pool_index + length]);
+            self.pool_index += length;
+            filled += length;
+        }
+    }
+
+    fn regenerate_pool(&mut self) {
+        let mut chacha = ChaCha20::new(&self.key, &[0; 12], 0);
+        // the first block only provides the next key
+        let mut block = chacha.next_block();
+        self.key.copy_from_slice(&block[..KEY_LENGTH]);
+        zeroize(&mut block);
+        chacha.fill(&mut self.pool);
+        self.pool_index = 0;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> Default for InlinePool<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Drop for InlinePool<N> {
+    fn drop(&mut self) {
+        zeroize(&mut self.pool);
+        zeroize(&mut self.key);
+    }
+}
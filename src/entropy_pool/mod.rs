@@ -1,131 +1,380 @@
+mod chacha;
+mod clock;
+#[cfg(feature = "std")]
 mod cpu_features;
 mod generator;
+mod health;
+mod inline;
+mod regeneration;
+mod report;
+mod seed;
+mod sha256;
+pub mod sources;
+mod zeroize;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::module_inception, clippy::mut_range_bound)]
 mod tests;
 
-use generator::{generate_entropy_pool, generate_seeded_pool};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use chacha::KEY_LENGTH;
+use clock::Stopwatch;
+
+use health::HealthState;
+pub use health::{HealthError, SourceHealth};
+pub use inline::InlinePool;
+use generator::{absorb_entropy, fill_entropy_pool, fill_seeded_pool};
+pub use generator::DEFAULT_POOL_SIZE;
+pub use regeneration::{RegenerationEvent, RegenerationHook, RegenerationReason, ReseedPolicy};
+pub use report::{EntropyReport, SourceReport};
+pub use seed::{seed_from_bytes, seed_from_u64, stretch_passphrase, PASSPHRASE_ROUNDS};
+#[cfg(feature = "std")]
+use seed::SEED_LENGTH;
+#[cfg(feature = "std")]
+use sources::default_sources;
+use sources::EntropySource;
+use zeroize::zeroize;
 
 /// `EntropyPool` is a struct that contains a pool of pseudo random bytes.
 /// The pool will regenerate itself if it is empty.
 pub struct EntropyPool {
     pool: Vec<u8>,
     pool_index: usize,
-    restricted_pool: bool,
     initial_pool_size: usize,
     seeded_iteration: u32,
     seed: Option<Vec<u8>>,
+    sources: Vec<Box<dyn EntropySource>>,
+    // The state of the health tests of every source, continued by every generation.
+    health: Vec<HealthState>,
+    report: EntropyReport,
+    // Entropy carried over from every generation into the next.
+    chain: [u8; KEY_LENGTH],
+    on_regenerate: Option<RegenerationHook>,
+    reseed_policy: ReseedPolicy,
+    last_regeneration: Stopwatch,
+    // Entropy added by the user, mixed into the key of every pool generated after it was added.
+    added_entropy: Option<[u8; KEY_LENGTH]>,
+    zeroize_consumed: bool,
+    // The pool has been freed by `release_pool`, the next one continues instead of starting over.
+    released: bool,
 }
 
 impl EntropyPool {
     /// Creates a new `EntropyPool`.
+    #[cfg(feature = "std")]
     pub fn new() -> Self {
-        let pool = generate_entropy_pool();
-        Self {
-            initial_pool_size: pool.len(),
-            pool,
-            pool_index: 0,
-            restricted_pool: false,
-            seeded_iteration: 0,
-            seed: None,
+        Self::create_with_sources(default_sources())
+    }
+
+    /// Creates a new `EntropyPool` using the given entropy sources.
+    pub fn create_with_sources(sources: Vec<Box<dyn EntropySource>>) -> Self {
+        Self::create_size_restricted_with_sources(DEFAULT_POOL_SIZE, sources)
+    }
+
+    /// Creates a new `EntropyPool` using the given entropy sources, failing if any source fails
+    /// its health tests.
+    pub fn try_create_with_sources(sources: Vec<Box<dyn EntropySource>>) -> Result<Self, HealthError> {
+        let entropy_pool = Self::create_with_sources(sources);
+        let failed_sources: Vec<SourceHealth> = entropy_pool
+            .report
+            .sources
+            .iter()
+            .filter(|source| !source.health.passed())
+            .map(|source| source.health.clone())
+            .collect();
+        if failed_sources.is_empty() {
+            Ok(entropy_pool)
+        } else {
+            Err(HealthError { failed_sources })
         }
     }
 
     pub fn create_seeded(seed: Vec<u8>) -> Self {
-        let pool = generate_seeded_pool(&seed, 0);
-        Self {
-            initial_pool_size: pool.len(),
-            pool,
-            pool_index: 0,
-            restricted_pool: false,
-            seeded_iteration: 0,
-            seed: Some(seed)
-        }
+        Self::create_seeded_size_restricted(DEFAULT_POOL_SIZE, seed)
+    }
+
+    /// Creates a new seeded `EntropyPool`, with the seed derived from system entropy.
+    #[cfg(feature = "std")]
+    pub fn create_replayable() -> Self {
+        let mut seed = vec![0u8; SEED_LENGTH];
+        fill_entropy_pool(
+            &mut seed,
+            &mut default_sources(),
+            &mut Vec::new(),
+            &mut EntropyReport::default(),
+            &mut [0; KEY_LENGTH],
+            None,
+        );
+        Self::create_seeded(seed)
     }
 
     /// Creates a new `EntropyPool` with a restricted pool size.
+    #[cfg(feature = "std")]
     pub fn create_size_restricted(initial_pool_size: usize) -> Self {
-        let pool = generate_restricted_pool(initial_pool_size);
-        Self {
-            initial_pool_size,
-            pool,
-            pool_index: 0,
-            restricted_pool: true,
-            seeded_iteration: 0,
-            seed: None,
-        }
+        Self::create_size_restricted_with_sources(initial_pool_size, default_sources())
+    }
+
+    /// Creates a new `EntropyPool` with a restricted pool size using the given entropy sources.
+    pub fn create_size_restricted_with_sources(
+        initial_pool_size: usize,
+        sources: Vec<Box<dyn EntropySource>>,
+    ) -> Self {
+        let mut entropy_pool = Self::create_lazy_with_sources(initial_pool_size, sources);
+        entropy_pool.warm_up();
+        entropy_pool
+    }
+
+    /// Creates a new `EntropyPool` with a restricted pool size using the given entropy sources.
+    /// Nothing is collected or allocated until the pool is first used, or `warm_up` is called.
+    pub fn create_lazy_with_sources(initial_pool_size: usize, sources: Vec<Box<dyn EntropySource>>) -> Self {
+        Self::from_parts(initial_pool_size, None, sources)
     }
 
     pub fn create_seeded_size_restricted(initial_pool_size: usize, seed: Vec<u8>) -> Self {
-        let pool = generate_seeded_restricted_pool(&seed, initial_pool_size, 0);
+        let mut entropy_pool = Self::from_parts(initial_pool_size, Some(seed), Vec::new());
+        entropy_pool.warm_up();
+        entropy_pool
+    }
+
+    fn from_parts(initial_pool_size: usize, seed: Option<Vec<u8>>, sources: Vec<Box<dyn EntropySource>>) -> Self {
         Self {
+            // generated by `warm_up`
+            pool: Vec::new(),
             initial_pool_size,
-            pool,
             pool_index: 0,
-            restricted_pool: true,
-            seeded_iteration: 0,
-            seed: Some(seed),
+            // iteration 0 is the initial pool
+            seeded_iteration: 1,
+            seed,
+            sources,
+            health: Vec::new(),
+            report: EntropyReport::default(),
+            chain: [0; KEY_LENGTH],
+            on_regenerate: None,
+            reseed_policy: ReseedPolicy::default(),
+            last_regeneration: Stopwatch::start(),
+            added_entropy: None,
+            zeroize_consumed: false,
+            released: false,
+        }
+    }
+
+    /// Generates the initial pool, unless it has already been generated.
+    /// Regenerates the pool if it has been released.
+    pub fn warm_up(&mut self) {
+        if !self.pool.is_empty() {
+            return;
+        }
+        if self.released {
+            self.regenerate_pool(RegenerationReason::Released);
+            return;
+        }
+        self.pool = vec![0u8; self.initial_pool_size];
+        match &self.seed {
+            // iteration 0 is the initial pool
+            Some(seed) => fill_seeded_pool(&mut self.pool, seed, 0, self.added_entropy.as_ref()),
+            None => fill_entropy_pool(
+                &mut self.pool,
+                &mut self.sources,
+                &mut self.health,
+                &mut self.report,
+                &mut self.chain,
+                self.added_entropy.as_ref(),
+            ),
         }
+        self.last_regeneration = Stopwatch::start();
     }
 
     /// Returns a byte from the pool.
     /// If the pool is empty, it will regenerate the pool.
     pub fn get_random_byte(&mut self) -> u8 {
+        if self.pool.is_empty() {
+            self.warm_up();
+        }
         if self.initial_pool_size == self.pool_index + 1 {
-            self.regenerate_pool();
+            if self.reseed_policy == ReseedPolicy::Never {
+                self.pool_index = 0;
+            } else {
+                self.regenerate_pool(RegenerationReason::Exhausted);
+            }
+        } else {
+            match self.reseed_policy {
+                ReseedPolicy::EveryBytes(limit) if self.pool_index >= limit => {
+                    self.regenerate_pool(RegenerationReason::ByteLimitReached);
+                }
+                ReseedPolicy::Every(interval) if self.interval_elapsed(interval) => {
+                    self.regenerate_pool(RegenerationReason::IntervalElapsed);
+                }
+                _ => {}
+            }
         }
         let out = self.pool[self.pool_index];
         self.pool_index += 1;
+        self.clear_consumed(1);
         out
     }
 
-    fn regenerate_pool(&mut self) {
-        if self.restricted_pool {
-            if self.seed.is_some() {
-                self.pool = generate_seeded_restricted_pool(self.seed.as_ref().unwrap(), self.initial_pool_size, self.seeded_iteration);
-                self.seeded_iteration += 1;
-                self.pool_index = 0;
-            } else {
-                self.pool = generate_restricted_pool(self.initial_pool_size);
-                self.pool_index = 0;
-            }
+    /// Returns `L` bytes from the pool, the same bytes `L` calls to `get_random_byte` return.
+    ///
+    /// Bytes within the pool are copied at once, e.g. a `u64` as a single word. Only if the pool
+    /// is exhausted or the reseed policy is due within the `L` bytes, they are read one by one.
+    pub fn get_random_bytes<const L: usize>(&mut self) -> [u8; L] {
+        if self.readable() >= L {
+            let mut out = [0; L];
+            out.copy_from_slice(&self.pool[self.pool_index..self.pool_index + L]);
+            self.pool_index += L;
+            self.clear_consumed(L);
+            out
         } else {
-            if self.seed.is_some() {
-                self.pool = generate_seeded_pool(self.seed.as_ref().unwrap(), self.seeded_iteration);
-                self.initial_pool_size = self.pool.len();
-                self.seeded_iteration += 1;
-                self.pool_index = 0;
+            core::array::from_fn(|_| self.get_random_byte())
+        }
+    }
+
+    /// Fills `dest` with bytes from the pool, the same bytes `dest.len()` calls to
+    /// `get_random_byte` return.
+    ///
+    /// The pool is copied in slices, regenerating it whenever it is exhausted or the reseed policy
+    /// is due.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            let length = self.readable().min(dest.len() - filled);
+            if length == 0 {
+                dest[filled] = self.get_random_byte();
+                filled += 1;
             } else {
-                self.pool = generate_entropy_pool();
-                self.initial_pool_size = self.pool.len();
-                self.pool_index = 0;
+                dest[filled..filled + length].copy_from_slice(&self.pool[self.pool_index..self.pool_index + length]);
+                self.pool_index += length;
+                self.clear_consumed(length);
+                filled += length;
             }
         }
     }
-}
 
-fn generate_seeded_restricted_pool(seed: &Vec<u8>, initial_pool_size: usize, iterations: u32) -> Vec<u8> {
-    let large_pool = generate_seeded_pool(seed, iterations);
-    if large_pool.len() < initial_pool_size {
-        let mut enlarged_pool = large_pool;
-        while enlarged_pool.len() < initial_pool_size {
-            enlarged_pool.append(&mut generate_seeded_pool(seed, iterations));
+    /// Zeroes the `length` bytes handed out last, if enabled.
+    fn clear_consumed(&mut self, length: usize) {
+        // a cycled pool hands out its bytes again
+        if self.zeroize_consumed && self.reseed_policy != ReseedPolicy::Never {
+            zeroize(&mut self.pool[self.pool_index - length..self.pool_index]);
+        }
+    }
+
+    /// Sets whether bytes are zeroed in the pool once they have been handed out.
+    pub fn set_zeroize_consumed(&mut self, zeroize_consumed: bool) {
+        self.zeroize_consumed = zeroize_consumed;
+    }
+
+    /// Zeroes and frees the pool. The next request regenerates it.
+    pub fn release_pool(&mut self) {
+        if self.pool.is_empty() {
+            // never generated or already released
+            return;
+        }
+        zeroize(&mut self.pool);
+        self.pool = Vec::new();
+        self.pool_index = 0;
+        self.released = true;
+    }
+
+    /// Returns how many bytes `get_random_byte` hands out before it regenerates or cycles the pool.
+    fn readable(&self) -> usize {
+        if self.pool.is_empty() {
+            // `get_random_byte` generates the initial pool first
+            return 0;
+        }
+        // the last byte of the pool is never handed out, `get_random_byte` regenerates instead
+        let in_pool = (self.initial_pool_size - 1).saturating_sub(self.pool_index);
+        match self.reseed_policy {
+            ReseedPolicy::EveryBytes(limit) => in_pool.min(limit.saturating_sub(self.pool_index)),
+            ReseedPolicy::Every(interval) if self.interval_elapsed(interval) => 0,
+            _ => in_pool,
+        }
+    }
+
+    /// Returns `true` if the pool is older than `interval`. Always `false` without a clock.
+    fn interval_elapsed(&self, interval: Duration) -> bool {
+        clock::CLOCK_AVAILABLE && self.last_regeneration.elapsed() >= interval
+    }
+
+    /// Returns the report of the entropy the pool was last generated from.
+    pub fn report(&self) -> &EntropyReport {
+        &self.report
+    }
+
+    /// Returns the seed of the pool, if seeded.
+    pub fn seed(&self) -> Option<&[u8]> {
+        self.seed.as_deref()
+    }
+
+    /// Sets when the pool regenerates itself.
+    pub fn set_reseed_policy(&mut self, reseed_policy: ReseedPolicy) {
+        self.reseed_policy = reseed_policy;
+    }
+
+    /// Mixes `data` into every pool generated from now on.
+    pub fn add_entropy(&mut self, data: &[u8]) {
+        self.added_entropy = Some(absorb_entropy(self.added_entropy.as_ref(), data));
+    }
+
+    /// Mixes `data` into the pool and regenerates it immediately.
+    pub fn reseed(&mut self, data: &[u8]) {
+        self.add_entropy(data);
+        self.regenerate_pool(RegenerationReason::Reseed);
+    }
+
+    /// Sets the observer called after every regeneration of the pool.
+    pub fn set_on_regenerate(&mut self, hook: Option<RegenerationHook>) {
+        self.on_regenerate = hook;
+    }
+
+    fn regenerate_pool(&mut self, reason: RegenerationReason) {
+        let start = Stopwatch::start();
+        let iteration = self.seeded_iteration;
+        if self.pool.is_empty() {
+            // regenerated before it was first used or after it was released, there is no pool to
+            // replace
+            self.pool = vec![0u8; self.initial_pool_size];
+            self.released = false;
+        }
+        // the pool is regenerated in place, it never changes its size
+        if let Some(seed) = &self.seed {
+            fill_seeded_pool(&mut self.pool, seed, iteration, self.added_entropy.as_ref());
+            self.seeded_iteration = self.seeded_iteration.wrapping_add(1);
+        } else {
+            fill_entropy_pool(
+                &mut self.pool,
+                &mut self.sources,
+                &mut self.health,
+                &mut self.report,
+                &mut self.chain,
+                self.added_entropy.as_ref(),
+            );
+        }
+        self.pool_index = 0;
+        self.last_regeneration = Stopwatch::start();
+        if let Some(hook) = self.on_regenerate.as_mut() {
+            hook(RegenerationEvent {
+                reason,
+                pool_size: self.pool.len(),
+                seeded_iteration: self.seed.as_ref().map(|_| iteration),
+                elapsed: start.elapsed(),
+            });
         }
-        enlarged_pool[0..initial_pool_size].to_vec()
-    } else {
-        large_pool[0..initial_pool_size].to_vec()
     }
 }
 
-fn generate_restricted_pool(initial_pool_size: usize) -> Vec<u8> {
-    let large_pool = generate_entropy_pool();
-    if large_pool.len() < initial_pool_size {
-        let mut enlarged_pool = large_pool;
-        while enlarged_pool.len() < initial_pool_size {
-            enlarged_pool.append(&mut generate_entropy_pool());
+impl Drop for EntropyPool {
+    fn drop(&mut self) {
+        zeroize(&mut self.pool);
+        if let Some(seed) = self.seed.as_mut() {
+            zeroize(seed);
+        }
+        zeroize(&mut self.chain);
+        if let Some(added) = self.added_entropy.as_mut() {
+            zeroize(added);
         }
-        enlarged_pool[0..initial_pool_size].to_vec()
-    } else {
-        large_pool[0..initial_pool_size].to_vec()
     }
 }
@@ -0,0 +1,60 @@
+use alloc::boxed::Box;
+use core::time::Duration;
+
+/// The reason an entropy pool was regenerated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegenerationReason {
+    /// Every byte of the pool has been handed out.
+    Exhausted,
+    /// The number of bytes allowed by `ReseedPolicy::EveryBytes` has been handed out.
+    ByteLimitReached,
+    /// The pool has been in use longer than allowed by `ReseedPolicy::Every`.
+    IntervalElapsed,
+    /// `Fortuna::reseed` was called.
+    Reseed,
+    /// The pool has been released with `Fortuna::release_pool` and is needed again.
+    Released,
+}
+
+/// When the entropy pool regenerates itself.
+///
+/// Regardless of the policy, added entropy (`Fortuna::add_entropy`) is mixed into every pool
+/// generated after it was added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReseedPolicy {
+    /// Regenerate the pool once every byte has been handed out.
+    #[default]
+    OnExhaustion,
+    /// Regenerate the pool after the given number of bytes has been handed out, or once it is
+    /// exhausted, whichever comes first.
+    EveryBytes(usize),
+    /// Regenerate the pool once it has been in use for the given duration, or once it is
+    /// exhausted, whichever comes first.
+    ///
+    /// Without a clock, i.e. without the `std` feature or on `wasm32-unknown-unknown`, no time
+    /// elapses and this behaves like `OnExhaustion`.
+    Every(Duration),
+    /// Never regenerate the pool. Once it is exhausted, it is cycled through from the start again.
+    Never,
+}
+
+/// Information about a single regeneration of the entropy pool.
+///
+/// Passed to the observer set with `Fortuna::on_regenerate` after every regeneration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegenerationEvent {
+    /// Why the pool was regenerated.
+    pub reason: RegenerationReason,
+    /// The size of the new pool in bytes.
+    pub pool_size: usize,
+    /// The iteration the new pool was generated with, if the pool is seeded.
+    /// `None` for pools using system entropy.
+    pub seeded_iteration: Option<u32>,
+    /// The time spent generating the new pool.
+    /// Always zero without the `std` feature.
+    pub elapsed: Duration,
+}
+
+/// Observer called after every regeneration of the entropy pool.
+pub type RegenerationHook = Box<dyn FnMut(RegenerationEvent) + Send + Sync>;
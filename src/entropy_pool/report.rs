@@ -0,0 +1,91 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::entropy_pool::health::SourceHealth;
+
+/// A report of the entropy an entropy pool was generated from.
+///
+/// Covers the last time the pool was generated. Seeded pools do not use entropy sources, so
+/// their report is empty.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EntropyReport {
+    /// A report for every entropy source, in the order they were collected from.
+    pub sources: Vec<SourceReport>,
+    /// `true` if no source contributed anything, so a pre-generated salt was used instead.
+    pub fell_back_to_pre_generated: bool,
+}
+
+impl EntropyReport {
+    /// Returns the estimated total bits of entropy the pool was generated from.
+    ///
+    /// This is the sum of the entropy of every contributing source, taking the lower of the
+    /// source's own estimate and the min-entropy heuristic of its samples. As rough as the
+    /// heuristic, see `SourceReport::min_entropy`.
+    pub fn total_entropy(&self) -> f64 {
+        self.sources
+            .iter()
+            .filter(|source| source.contributed())
+            .map(|source| source.min_entropy.min(source.estimated_entropy as f64))
+            .sum()
+    }
+}
+
+/// A report of a single entropy source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceReport {
+    /// The name of the source.
+    pub name: String,
+    /// The number of bytes the source collected.
+    pub bytes: usize,
+    /// `true` if the source used a fallback instead of its real data, e.g. a pre-generated salt
+    /// because no CPU features could be detected.
+    pub fell_back: bool,
+    /// The bits of entropy the source estimates it provided.
+    pub estimated_entropy: u32,
+    /// A rough heuristic of the bits of min-entropy of the collected bytes, using the most common
+    /// value estimate of SP 800-90B on this one collection.
+    ///
+    /// It is not a bound in either direction. SP 800-90B assesses a source on a million samples,
+    /// a collection has a few dozen bytes, so the figure is meaningless for small collections:
+    /// Even a source returning the same distinct bytes every time gets a high figure. Samples that
+    /// look random, e.g. digests, can also still be predictable. Always zero without the `std`
+    /// feature.
+    pub min_entropy: f64,
+    /// The results of the health tests of the source.
+    pub health: SourceHealth,
+}
+
+impl SourceReport {
+    /// Returns `true` if the source passed its health tests and collected anything, meaning it
+    /// contributed to the pool.
+    pub fn contributed(&self) -> bool {
+        self.bytes > 0 && self.health.passed()
+    }
+}
+
+/// Estimates the bits of min-entropy of `samples`, treating every byte as a sample.
+///
+/// Uses the most common value estimate: The upper bound of the 99% confidence interval of the
+/// probability of the most common byte. Only a rough heuristic, see `SourceReport::min_entropy`.
+#[cfg(feature = "std")]
+pub fn estimate_min_entropy(samples: &[u8]) -> f64 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let mut counts = [0usize; 256];
+    for sample in samples {
+        counts[*sample as usize] += 1;
+    }
+    let most_common = counts.iter().max().copied().unwrap_or(0);
+    let n = samples.len() as f64;
+    let probability = most_common as f64 / n;
+    let upper_bound = (probability + 2.576 * (probability * (1.0 - probability) / (n - 1.0)).sqrt()).min(1.0);
+    // min-entropy per byte, times the number of bytes
+    -upper_bound.log2() * n
+}
+
+/// Without the standard library there is no floating point math to estimate with.
+#[cfg(not(feature = "std"))]
+pub fn estimate_min_entropy(_samples: &[u8]) -> f64 {
+    0.0
+}
@@ -0,0 +1,44 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::entropy_pool::generator::splitmix64;
+use crate::entropy_pool::sha256::{hmac_sha256, pbkdf2_hmac_sha256};
+
+/// The length of seeds produced by the functions in this module.
+pub const SEED_LENGTH: usize = 32;
+
+/// The number of PBKDF2 rounds `from_passphrase` uses, the OWASP recommendation for
+/// PBKDF2-HMAC-SHA-256.
+pub const PASSPHRASE_ROUNDS: u32 = 600_000;
+
+/// Separates the seeds derived from bytes from any other use of HMAC-SHA-256.
+const SEED_SALT: &[u8] = b"fortuna seed";
+
+/// The PBKDF2 salt of passphrases. It is fixed, the same passphrase has to derive the same seed.
+const PASSPHRASE_SALT: &[u8] = b"fortuna passphrase";
+
+/// Expands `value` into a seed of `SEED_LENGTH` bytes.
+pub fn seed_from_u64(value: u64) -> Vec<u8> {
+    let mut state = value;
+    let mut seed = Vec::with_capacity(SEED_LENGTH);
+    while seed.len() < SEED_LENGTH {
+        seed.extend_from_slice(&splitmix64(&mut state).to_le_bytes());
+    }
+    seed
+}
+
+/// Compresses `data` of any length into a seed of `SEED_LENGTH` bytes with HMAC-SHA-256.
+///
+/// Every byte of the seed depends on every byte of `data`.
+pub fn seed_from_bytes(data: &[u8]) -> Vec<u8> {
+    hmac_sha256(SEED_SALT, &[data]).to_vec()
+}
+
+/// Derives a seed of `SEED_LENGTH` bytes from `passphrase` with PBKDF2-HMAC-SHA-256.
+///
+/// Every guess of the passphrase costs `rounds` HMAC-SHA-256 computations.
+pub fn stretch_passphrase(passphrase: &[u8], rounds: u32) -> Vec<u8> {
+    let mut seed = vec![0u8; SEED_LENGTH];
+    pbkdf2_hmac_sha256(passphrase, PASSPHRASE_SALT, rounds, &mut seed);
+    seed
+}
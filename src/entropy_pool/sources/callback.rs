@@ -0,0 +1,43 @@
+use alloc::vec::Vec;
+
+use super::EntropySource;
+
+/// The number of bytes requested from the callback on every collection.
+const CALLBACK_BYTES: usize = 32;
+
+/// Collects entropy from a user-supplied callback, e.g. reading a hardware random number
+/// generator on an embedded target.
+///
+/// The callback fills the given buffer with random bytes. Every byte is expected to carry full
+/// entropy; if it does not, wrap it in a custom `EntropySource` with a lower estimate instead.
+pub struct CallbackSource<F> {
+    callback: F,
+}
+
+impl<F> CallbackSource<F>
+where
+    F: FnMut(&mut [u8]) + Send + Sync,
+{
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<F> EntropySource for CallbackSource<F>
+where
+    F: FnMut(&mut [u8]) + Send + Sync,
+{
+    fn name(&self) -> &str {
+        "callback"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        let start = out.len();
+        out.resize(start + CALLBACK_BYTES, 0);
+        (self.callback)(&mut out[start..]);
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        CALLBACK_BYTES as u32 * 8
+    }
+}
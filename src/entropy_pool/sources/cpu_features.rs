@@ -0,0 +1,46 @@
+use crate::entropy_pool::cpu_features::get_cpu_features;
+
+use super::EntropySource;
+
+/// Collects the names of the features of the CPU.
+///
+/// CPU features do not change on the same machine, so this source only adds variation between
+/// machines.
+pub struct CpuFeaturesSource;
+
+impl EntropySource for CpuFeaturesSource {
+    fn name(&self) -> &str {
+        "cpu features"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        let cpu_features = get_cpu_features();
+        if cpu_features.is_empty() {
+            // if no CPU features detected, fallback to pre-generated salt
+            // As CPU features do not change on the same machine anyway, this should be fine
+            out.extend_from_slice(&PRE_GENERATED);
+        } else {
+            for feature in cpu_features {
+                out.extend_from_slice(feature.as_bytes());
+            }
+        }
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        0
+    }
+
+    fn fell_back(&self) -> bool {
+        get_cpu_features().is_empty()
+    }
+}
+
+const PRE_GENERATED: [u8; 125] = [
+    195, 15, 51, 98, 244, 101, 246, 245, 194, 184, 82, 102, 170, 119, 58, 233, 92, 9, 91, 170, 15,
+    45, 220, 17, 34, 110, 241, 177, 33, 227, 14, 50, 197, 23, 198, 83, 218, 168, 34, 18, 49, 224,
+    42, 160, 178, 80, 218, 43, 27, 225, 50, 240, 65, 187, 133, 206, 17, 123, 135, 130, 153, 107,
+    185, 84, 156, 45, 232, 19, 192, 15, 198, 95, 147, 240, 150, 180, 210, 254, 149, 133, 99, 250,
+    111, 183, 211, 6, 135, 95, 120, 33, 154, 209, 42, 238, 28, 107, 130, 110, 164, 8, 212, 103, 28,
+    56, 240, 41, 166, 149, 142, 96, 254, 155, 214, 156, 51, 0, 105, 21, 171, 65, 177, 165, 234, 35,
+    230,
+];
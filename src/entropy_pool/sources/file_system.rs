@@ -0,0 +1,105 @@
+use std::path::Path;
+
+// linux spec use:
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::os::unix::fs::MetadataExt;
+
+// Windows spec use
+#[cfg(target_os = "windows")]
+use std::os::windows::fs::MetadataExt;
+
+use super::EntropySource;
+
+/// Collects properties of the file system, like the device ID and inode number of the root
+/// directory, the nesting depth of the current working directory and the metadata of the
+/// working directory and the executable.
+///
+/// The salt will rarely change on the same machine, if executed from the same path.
+pub struct FileSystemSource;
+
+impl EntropySource for FileSystemSource {
+    fn name(&self) -> &str {
+        "file system"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        if let Ok(directory) = std::env::current_dir() {
+            out.append(&mut directory_salt(&directory));
+            out.append(&mut metadata_salt(&directory));
+        }
+        if let Ok(executable) = std::env::current_exe() {
+            out.append(&mut metadata_salt(&executable));
+        }
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        0
+    }
+}
+
+/// Returns the nesting depth of `directory` followed by the metadata of its root directory.
+pub fn directory_salt(directory: &Path) -> Vec<u8> {
+    let mut salt: Vec<u8> = Vec::new();
+    salt.extend_from_slice(&(directory.ancestors().count() as u64).to_le_bytes());
+    if let Some(root_dir) = directory.ancestors().last() {
+        salt.append(&mut metadata_salt(root_dir));
+    }
+    salt
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+/// Linux or mac: device ID, inode number, size, modification and status change time.
+/// Empty if the metadata can not be read.
+pub fn metadata_salt(path: &Path) -> Vec<u8> {
+    let mut salt: Vec<u8> = Vec::new();
+    if let Ok(metadata) = path.metadata() {
+        let values = [
+            metadata.dev(),
+            metadata.ino(),
+            metadata.size(),
+            metadata.mtime() as u64,
+            metadata.mtime_nsec() as u64,
+            metadata.ctime() as u64,
+            metadata.ctime_nsec() as u64,
+        ];
+        for value in values {
+            salt.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    salt
+}
+
+#[cfg(target_os = "windows")]
+/// Windows only: size, creation, last access and last write time.
+/// Empty if the metadata can not be read.
+pub fn metadata_salt(path: &Path) -> Vec<u8> {
+    let mut salt: Vec<u8> = Vec::new();
+    if let Ok(metadata) = path.metadata() {
+        let values = [
+            metadata.file_size(),
+            metadata.creation_time(),
+            metadata.last_access_time(),
+            metadata.last_write_time(),
+        ];
+        for value in values {
+            salt.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    salt
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+/// Other platforms: size and modification time, if the platform has a file system.
+/// Empty if the metadata can not be read.
+pub fn metadata_salt(path: &Path) -> Vec<u8> {
+    let mut salt: Vec<u8> = Vec::new();
+    if let Ok(metadata) = path.metadata() {
+        salt.extend_from_slice(&metadata.len().to_le_bytes());
+        if let Ok(modified) = metadata.modified() {
+            if let Ok(since_epoch) = modified.duration_since(std::time::UNIX_EPOCH) {
+                salt.extend_from_slice(&(since_epoch.as_nanos() as u64).to_le_bytes());
+            }
+        }
+    }
+    salt
+}
@@ -0,0 +1,183 @@
+use super::EntropySource;
+
+/// The number of 64 bit words collected from the hardware random number generator.
+const HARDWARE_RANDOM_WORDS: usize = 4;
+
+/// How often reading a single word is retried before giving up.
+/// The hardware may fail to deliver a word if it is drained, usually a few retries suffice.
+const RETRY_LIMIT: usize = 10;
+
+/// Collects random words from the hardware random number generator of the CPU.
+///
+/// On x86_64 `RDSEED` is used if available, falling back to `RDRAND` if it is not or keeps failing.
+/// On aarch64 `RNDR` is used if available.
+/// If no hardware random number generator is available or it keeps failing, nothing is collected.
+pub struct HardwareRandomSource {
+    // The number of words read by the last call to `collect`, `None` before the first call.
+    words: Option<usize>,
+    fell_back: bool,
+}
+
+impl HardwareRandomSource {
+    pub fn new() -> Self {
+        Self {
+            words: None,
+            fell_back: false,
+        }
+    }
+}
+
+impl Default for HardwareRandomSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntropySource for HardwareRandomSource {
+    fn name(&self) -> &str {
+        "hardware random"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        let mut words = 0;
+        self.fell_back = false;
+        for _ in 0..HARDWARE_RANDOM_WORDS {
+            let word = match read_hardware_word(try_read_hardware_word) {
+                Some(word) => Some(word),
+                None => {
+                    let word = read_hardware_word(try_read_fallback_word);
+                    self.fell_back |= word.is_some();
+                    word
+                }
+            };
+            match word {
+                Some(word) => out.extend_from_slice(&word.to_le_bytes()),
+                None => break,
+            }
+            words += 1;
+        }
+        self.words = Some(words);
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        match self.words {
+            Some(words) => (words * 64) as u32,
+            None if is_available() => (HARDWARE_RANDOM_WORDS * 64) as u32,
+            None => 0,
+        }
+    }
+
+    fn fell_back(&self) -> bool {
+        self.fell_back
+    }
+}
+
+/// Reads a single word with `try_read`, retrying up to `RETRY_LIMIT` times.
+fn read_hardware_word(try_read: fn() -> Option<u64>) -> Option<u64> {
+    for _ in 0..RETRY_LIMIT {
+        if let Some(word) = try_read() {
+            // Some CPUs are known to return all zeros or all ones once broken
+            if word != 0 && word != u64::MAX {
+                return Some(word);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_arch = "x86_64")]
+fn is_available() -> bool {
+    std::arch::is_x86_feature_detected!("rdseed") || std::arch::is_x86_feature_detected!("rdrand")
+}
+
+#[cfg(target_arch = "x86_64")]
+fn try_read_hardware_word() -> Option<u64> {
+    if std::arch::is_x86_feature_detected!("rdseed") {
+        // SAFETY: `rdseed` is supported, checked above.
+        unsafe { rdseed() }
+    } else {
+        None
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn try_read_fallback_word() -> Option<u64> {
+    if std::arch::is_x86_feature_detected!("rdrand") {
+        // SAFETY: `rdrand` is supported, checked above.
+        unsafe { rdrand() }
+    } else {
+        None
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "rdseed")]
+unsafe fn rdseed() -> Option<u64> {
+    let mut word = 0;
+    if std::arch::x86_64::_rdseed64_step(&mut word) == 1 {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "rdrand")]
+unsafe fn rdrand() -> Option<u64> {
+    let mut word = 0;
+    if std::arch::x86_64::_rdrand64_step(&mut word) == 1 {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+fn is_available() -> bool {
+    std::arch::is_aarch64_feature_detected!("rand")
+}
+
+#[cfg(target_arch = "aarch64")]
+fn try_read_hardware_word() -> Option<u64> {
+    if !std::arch::is_aarch64_feature_detected!("rand") {
+        return None;
+    }
+    let word: u64;
+    let ok: u64;
+    // SAFETY: `RNDR` is supported, checked above. It only writes the output register and the
+    // condition flags.
+    unsafe {
+        std::arch::asm!(
+            // RNDR, not every assembler knows the name
+            "mrs {word}, s3_3_c2_c4_0",
+            // RNDR sets the Z flag on failure
+            "cset {ok}, ne",
+            word = out(reg) word,
+            ok = out(reg) ok,
+            options(nomem, nostack),
+        );
+    }
+    if ok == 1 {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+/// No hardware random number generator supported
+fn is_available() -> bool {
+    false
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+/// No hardware random number generator supported
+fn try_read_hardware_word() -> Option<u64> {
+    None
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+/// No fallback to the hardware random number generator
+fn try_read_fallback_word() -> Option<u64> {
+    None
+}
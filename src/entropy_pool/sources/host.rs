@@ -0,0 +1,47 @@
+use alloc::vec::Vec;
+
+use super::EntropySource;
+use crate::entropy_pool::zeroize::zeroize;
+
+/// Provides entropy handed over by the host once, e.g. bytes from `crypto.getRandomValues` passed
+/// into a WebAssembly module.
+///
+/// The entropy is only used for the first generation and forgotten afterwards. Later generations
+/// still depend on it, as every generation carries entropy over into the next.
+pub struct HostEntropySource {
+    entropy: Vec<u8>,
+    provided: usize,
+}
+
+impl HostEntropySource {
+    pub fn new(entropy: &[u8]) -> Self {
+        Self {
+            entropy: entropy.to_vec(),
+            provided: 0,
+        }
+    }
+}
+
+impl EntropySource for HostEntropySource {
+    fn name(&self) -> &str {
+        "host entropy"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.entropy);
+        self.provided = self.entropy.len();
+        zeroize(&mut self.entropy);
+        self.entropy.clear();
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        self.provided.saturating_mul(8).min(u32::MAX as usize) as u32
+    }
+}
+
+impl Drop for HostEntropySource {
+    fn drop(&mut self) {
+        // the entropy has not been collected yet
+        zeroize(&mut self.entropy);
+    }
+}
@@ -0,0 +1,137 @@
+use std::hint::black_box;
+
+use crate::entropy_pool::clock::{Stopwatch, CLOCK_AVAILABLE};
+
+use super::EntropySource;
+
+/// The number of 64 bit words collected by default.
+const DEFAULT_WORDS: usize = 4;
+
+/// The number of accepted samples folded into every word.
+const SAMPLES_PER_WORD: usize = 64;
+
+/// How many samples are taken per accepted sample at most, before giving up on a timer that
+/// keeps getting stuck.
+const MAX_ATTEMPTS_PER_SAMPLE: usize = 4;
+
+/// Every accepted sample is credited with `1 / SAMPLES_PER_BIT` bits of entropy.
+const SAMPLES_PER_BIT: usize = 8;
+
+/// The size of the memory walked during every sample, larger than most L1 caches.
+const MEMORY_SIZE: usize = 64 * 1024;
+
+/// The distance between two memory accesses, larger than a cache line.
+const MEMORY_STRIDE: usize = 67;
+
+/// The minimum number of memory accesses per sample.
+const MEMORY_ACCESSES: usize = 64;
+
+/// The minimum number of branches per sample.
+const BRANCHES: usize = 64;
+
+/// Collects entropy from the jitter in the execution time of the CPU.
+///
+/// Every sample measures the time spent on memory-access and branch-heavy work, the amount of
+/// work depending on the previous measurement. Samples where the timer appears stuck (the time,
+/// or its first or second derivative, is zero) are discarded. The remaining samples are folded
+/// into 64 bit words.
+pub struct JitterSource {
+    words: usize,
+    accepted_samples: Option<usize>,
+}
+
+impl JitterSource {
+    /// Creates a new `JitterSource` collecting 4 words (32 bytes).
+    pub fn new() -> Self {
+        Self::with_words(DEFAULT_WORDS)
+    }
+
+    /// Creates a new `JitterSource` collecting `words` 64 bit words.
+    /// Every word takes 64 samples to collect.
+    pub fn with_words(words: usize) -> Self {
+        Self {
+            words,
+            accepted_samples: None,
+        }
+    }
+}
+
+impl Default for JitterSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntropySource for JitterSource {
+    fn name(&self) -> &str {
+        "cpu jitter"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        if !CLOCK_AVAILABLE {
+            // nothing to measure the jitter with
+            self.accepted_samples = Some(0);
+            return;
+        }
+        let mut memory = vec![0u8; MEMORY_SIZE];
+        let mut memory_index = 0;
+        let mut previous_delta: u64 = 0;
+        let mut previous_delta1: u64 = 0;
+        let mut accepted_samples = 0;
+        let mut attempts = 0;
+        let max_attempts = self.words * SAMPLES_PER_WORD * MAX_ATTEMPTS_PER_SAMPLE;
+
+        for _ in 0..self.words {
+            let mut word: u64 = 0;
+            let mut folded = 0;
+            while folded < SAMPLES_PER_WORD && attempts < max_attempts {
+                attempts += 1;
+                let start = Stopwatch::start();
+
+                // memory access, how much depends on the previous sample
+                let memory_accesses = MEMORY_ACCESSES + (previous_delta & 0x3f) as usize;
+                for _ in 0..memory_accesses {
+                    memory_index = (memory_index + MEMORY_STRIDE) % MEMORY_SIZE;
+                    memory[memory_index] = memory[memory_index].wrapping_add(1) ^ memory_index as u8;
+                }
+
+                // data dependent branches
+                let mut branch_state = black_box(previous_delta) | 1;
+                for _ in 0..BRANCHES + (previous_delta >> 6 & 0x3f) as usize {
+                    if branch_state & 1 == 1 {
+                        branch_state = branch_state.wrapping_mul(3).wrapping_add(1);
+                    } else {
+                        branch_state >>= 1;
+                    }
+                }
+                black_box(branch_state);
+
+                let delta = start.elapsed().as_nanos() as u64;
+                let delta1 = delta.wrapping_sub(previous_delta);
+                let delta2 = delta1.wrapping_sub(previous_delta1);
+                previous_delta = delta;
+                previous_delta1 = delta1;
+
+                // stuck test
+                if delta == 0 || delta1 == 0 || delta2 == 0 {
+                    continue;
+                }
+                word = word.rotate_left(7) ^ delta;
+                folded += 1;
+            }
+            accepted_samples += folded;
+            if folded < SAMPLES_PER_WORD {
+                // the timer is stuck too often to be useful
+                break;
+            }
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+        black_box(&memory);
+        self.accepted_samples = Some(accepted_samples);
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        let samples = self.accepted_samples.unwrap_or(self.words * SAMPLES_PER_WORD);
+        (samples / SAMPLES_PER_BIT) as u32
+    }
+}
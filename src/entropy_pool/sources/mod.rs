@@ -0,0 +1,125 @@
+//! Sources of entropy used to generate unseeded entropy pools.
+//!
+//! Every source implements `EntropySource`. The built-in sources are listed by
+//! `default_sources`, custom sources can be added by implementing the trait.
+//! Without the `std` feature there are no built-in sources, entropy has to be provided by the
+//! user, e.g. with a `CallbackSource`.
+//!
+//! ## Example
+//! ```
+//! use fortuna::Fortuna;
+//! use fortuna::sources::{default_sources, EntropySource};
+//!
+//! struct RequestIdSource {
+//!     request_id: u64,
+//! }
+//!
+//! impl EntropySource for RequestIdSource {
+//!     fn name(&self) -> &str {
+//!         "request id"
+//!     }
+//!
+//!     fn collect(&mut self, out: &mut Vec<u8>) {
+//!         out.extend_from_slice(&self.request_id.to_le_bytes());
+//!     }
+//!
+//!     fn estimated_entropy(&self) -> u32 {
+//!         8
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut sources = default_sources();
+//!     // The file system source is not very useful inside a container
+//!     sources.retain(|source| source.name() != "file system");
+//!     sources.push(Box::new(RequestIdSource { request_id: 1234 }));
+//!     let mut fortuna = Fortuna::create_with_sources(sources);
+//!     let random_number: u8 = fortuna.random_u8();
+//!     println!("Generated random u8: {}", random_number);
+//! }
+//! ```
+
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use alloc::vec;
+use alloc::vec::Vec;
+
+mod callback;
+#[cfg(feature = "std")]
+mod cpu_features;
+#[cfg(feature = "std")]
+mod file_system;
+#[cfg(feature = "std")]
+mod hardware_random;
+mod host;
+#[cfg(feature = "std")]
+mod jitter;
+#[cfg(feature = "std")]
+mod os_random;
+#[cfg(feature = "std")]
+mod process;
+#[cfg(feature = "std")]
+mod system_time;
+#[cfg(feature = "std")]
+mod timing;
+
+pub use callback::CallbackSource;
+#[cfg(feature = "std")]
+pub use cpu_features::CpuFeaturesSource;
+#[cfg(feature = "std")]
+pub use file_system::FileSystemSource;
+#[cfg(all(test, feature = "std", target_os = "linux"))]
+pub(crate) use file_system::{directory_salt, metadata_salt};
+#[cfg(feature = "std")]
+pub use hardware_random::HardwareRandomSource;
+pub use host::HostEntropySource;
+#[cfg(feature = "std")]
+pub use jitter::JitterSource;
+#[cfg(feature = "std")]
+pub use os_random::OsRandomSource;
+#[cfg(feature = "std")]
+pub use process::ProcessSource;
+#[cfg(feature = "std")]
+pub use system_time::SystemTimeSource;
+#[cfg(feature = "std")]
+pub use timing::TimingSource;
+
+/// A source of entropy for unseeded entropy pools.
+///
+/// Every time an entropy pool is generated, `collect` is called once on every source.
+/// The time every source takes to collect is measured and used as entropy as well.
+pub trait EntropySource: Send + Sync {
+    /// The name of the source, e.g. `"system time"`.
+    fn name(&self) -> &str;
+
+    /// Collects entropy, appending it to `out`.
+    ///
+    /// Must not panic. If no entropy is available, nothing should be appended.
+    fn collect(&mut self, out: &mut Vec<u8>);
+
+    /// A rough estimate of the bits of entropy a single call to `collect` provides.
+    fn estimated_entropy(&self) -> u32;
+
+    /// Returns `true` if the last call to `collect` used a fallback instead of the real data of
+    /// the source.
+    fn fell_back(&self) -> bool {
+        false
+    }
+}
+
+/// Returns the built-in entropy sources used by `Fortuna::new`.
+///
+/// Only available with the `std` feature, as every built-in source needs the standard library.
+#[cfg(feature = "std")]
+pub fn default_sources() -> Vec<Box<dyn EntropySource>> {
+    vec![
+        Box::new(OsRandomSource::new()),
+        Box::new(HardwareRandomSource::new()),
+        Box::new(SystemTimeSource),
+        Box::new(JitterSource::new()),
+        Box::new(ProcessSource),
+        Box::new(CpuFeaturesSource),
+        Box::new(FileSystemSource),
+    ]
+}
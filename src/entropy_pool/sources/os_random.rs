@@ -0,0 +1,134 @@
+use super::EntropySource;
+
+/// The number of bytes collected from the operating system.
+const OS_RANDOM_LENGTH: usize = 32;
+
+/// Collects random bytes from the operating system.
+///
+/// On Linux on x86_64 and aarch64 the `getrandom(2)` syscall is used, falling back to reading
+/// `/dev/urandom`. On other unix systems `/dev/urandom` is read.
+/// If neither is available, nothing is collected and the other sources have to make up for it.
+pub struct OsRandomSource {
+    fell_back: bool,
+    failed: bool,
+}
+
+impl OsRandomSource {
+    pub fn new() -> Self {
+        Self {
+            fell_back: false,
+            failed: false,
+        }
+    }
+}
+
+impl Default for OsRandomSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntropySource for OsRandomSource {
+    fn name(&self) -> &str {
+        "os random"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        let mut buffer = [0u8; OS_RANDOM_LENGTH];
+        self.fell_back = false;
+        self.failed = false;
+        if get_random(&mut buffer) {
+            out.extend_from_slice(&buffer);
+        } else if read_urandom(&mut buffer) {
+            self.fell_back = true;
+            out.extend_from_slice(&buffer);
+        } else {
+            self.failed = true;
+        }
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        if self.failed {
+            0
+        } else {
+            (OS_RANDOM_LENGTH * 8) as u32
+        }
+    }
+
+    fn fell_back(&self) -> bool {
+        self.fell_back
+    }
+}
+
+/// Do not block if the kernel entropy pool is not initialised yet, fall back instead
+#[cfg(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64")))]
+const GRND_NONBLOCK: usize = 0x0001;
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+/// Fills `buffer` using the `getrandom(2)` syscall.
+/// Returns `false` if the buffer could not be filled completely.
+fn get_random(buffer: &mut [u8]) -> bool {
+    const SYS_GETRANDOM: usize = 318;
+    let filled: isize;
+    // SAFETY: `buffer` is valid for writes of `buffer.len()` bytes. The syscall only writes the
+    // buffer, the result register and the registers clobbered by `syscall`.
+    unsafe {
+        std::arch::asm!(
+            "syscall",
+            inlateout("rax") SYS_GETRANDOM as isize => filled,
+            in("rdi") buffer.as_mut_ptr(),
+            in("rsi") buffer.len(),
+            in("rdx") GRND_NONBLOCK,
+            lateout("rcx") _,
+            lateout("r11") _,
+            options(nostack),
+        );
+    }
+    filled == buffer.len() as isize
+}
+
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+/// Fills `buffer` using the `getrandom(2)` syscall.
+/// Returns `false` if the buffer could not be filled completely.
+fn get_random(buffer: &mut [u8]) -> bool {
+    const SYS_GETRANDOM: usize = 278;
+    let filled: isize;
+    // SAFETY: `buffer` is valid for writes of `buffer.len()` bytes. The syscall only writes the
+    // buffer and the result register.
+    unsafe {
+        std::arch::asm!(
+            "svc 0",
+            in("x8") SYS_GETRANDOM,
+            inlateout("x0") buffer.as_mut_ptr() as isize => filled,
+            in("x1") buffer.len(),
+            in("x2") GRND_NONBLOCK,
+            options(nostack),
+        );
+    }
+    filled == buffer.len() as isize
+}
+
+#[cfg(not(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64"))))]
+/// No `getrandom(2)` syscall available
+fn get_random(_buffer: &mut [u8]) -> bool {
+    false
+}
+
+#[cfg(unix)]
+/// Fills `buffer` by reading `/dev/urandom`.
+/// Returns `false` if the buffer could not be filled completely.
+fn read_urandom(buffer: &mut [u8]) -> bool {
+    use std::io::Read;
+
+    match std::fs::File::open("/dev/urandom") {
+        Ok(mut file) => file.read_exact(buffer).is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(unix))]
+/// No `/dev/urandom` available
+fn read_urandom(_buffer: &mut [u8]) -> bool {
+    false
+}
+
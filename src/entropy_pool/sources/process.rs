@@ -0,0 +1,81 @@
+#[cfg(target_os = "linux")]
+use crate::entropy_pool::sha256::sha256;
+
+use super::EntropySource;
+
+/// Files in `/proc` that change between processes or over time.
+/// Every file is optional, files that cannot be read are skipped.
+#[cfg(target_os = "linux")]
+const PROC_FILES: [&str; 5] = [
+    // counters of this process, e.g. page faults and CPU time
+    "/proc/self/stat",
+    // counters of every interrupt on every CPU
+    "/proc/interrupts",
+    // load average, number of running processes and the last PID
+    "/proc/loadavg",
+    // CPU time of the whole system
+    "/proc/stat",
+    // changes every boot
+    "/proc/sys/kernel/random/boot_id",
+];
+
+/// Collects the state of the current process, like its ID and the addresses it was loaded at,
+/// and on Linux counters from `/proc`.
+///
+/// This adds variation between processes started at the same instant.
+pub struct ProcessSource;
+
+impl EntropySource for ProcessSource {
+    fn name(&self) -> &str {
+        "process state"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        // there are no processes on wasm32-unknown-unknown, asking for the ID panics
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        out.extend_from_slice(&std::process::id().to_le_bytes());
+
+        // Addresses differ between processes because of ASLR
+        let stack_variable = 0u8;
+        let heap_variable = Box::new(0u8);
+        let stack_address = &stack_variable as *const u8 as usize;
+        let heap_address = &*heap_variable as *const u8 as usize;
+        let code_address = process_source_code_address as *const () as usize;
+        out.extend_from_slice(&stack_address.to_le_bytes());
+        out.extend_from_slice(&heap_address.to_le_bytes());
+        out.extend_from_slice(&code_address.to_le_bytes());
+
+        collect_proc(out);
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        // PID and ASLR offsets are partly guessable, /proc counters change slowly
+        if cfg!(target_os = "linux") {
+            32
+        } else {
+            16
+        }
+    }
+}
+
+/// Only used for its address.
+fn process_source_code_address() {}
+
+#[cfg(target_os = "linux")]
+/// Linux only
+fn collect_proc(out: &mut Vec<u8>) {
+    // "<pid>/task/<tid>"
+    if let Ok(thread) = std::fs::read_link("/proc/thread-self") {
+        out.extend_from_slice(thread.as_os_str().as_encoded_bytes());
+    }
+    for file in PROC_FILES {
+        if let Ok(content) = std::fs::read(file) {
+            // files like /proc/interrupts can be large, keep only a digest
+            out.extend_from_slice(&sha256(&content));
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+/// No /proc available
+fn collect_proc(_out: &mut Vec<u8>) {}
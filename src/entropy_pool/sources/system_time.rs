@@ -0,0 +1,83 @@
+use std::sync::OnceLock;
+
+use crate::entropy_pool::clock::{unix_time, Stopwatch, CLOCK_AVAILABLE};
+
+use super::EntropySource;
+
+/// Collects the current system time, the monotonic time and, where available, the CPU cycle
+/// counter.
+///
+/// All values are pushed as raw little-endian integers, least changing bytes last.
+/// Targets without a clock, like `wasm32-unknown-unknown`, only provide the cycle counter.
+pub struct SystemTimeSource;
+
+impl EntropySource for SystemTimeSource {
+    fn name(&self) -> &str {
+        "system time"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        if let Some(since_epoch) = unix_time() {
+            out.extend_from_slice(&(since_epoch.as_nanos() as u64).to_le_bytes());
+        }
+        if CLOCK_AVAILABLE {
+            out.extend_from_slice(&(monotonic_start().elapsed().as_nanos() as u64).to_le_bytes());
+        }
+        if let Some(cycles) = cycle_counter() {
+            out.extend_from_slice(&cycles.to_le_bytes());
+        }
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        // Only the sub-microsecond parts are hard to guess
+        16
+    }
+}
+
+/// The stopwatch the monotonic time is measured with, started on first use.
+fn monotonic_start() -> &'static Stopwatch {
+    static START: OnceLock<Stopwatch> = OnceLock::new();
+    START.get_or_init(Stopwatch::start)
+}
+
+/// Reads the cycle counter of the CPU, if there is one readable from user space.
+#[cfg(target_arch = "x86_64")]
+fn cycle_counter() -> Option<u64> {
+    // SAFETY: RDTSC is part of every x86_64 CPU.
+    #[allow(unused_unsafe)]
+    Some(unsafe { std::arch::x86_64::_rdtsc() })
+}
+
+/// Reads the cycle counter of the CPU, if there is one readable from user space.
+#[cfg(target_arch = "aarch64")]
+fn cycle_counter() -> Option<u64> {
+    let ticks: u64;
+    // SAFETY: the virtual counter is readable from user space on every aarch64 OS we run on.
+    unsafe {
+        std::arch::asm!("mrs {ticks}, cntvct_el0", ticks = out(reg) ticks, options(nomem, nostack));
+    }
+    Some(ticks)
+}
+
+/// Reads the cycle counter of the CPU, if there is one readable from user space.
+#[cfg(target_arch = "riscv64")]
+fn cycle_counter() -> Option<u64> {
+    let cycles: u64;
+    // Linux 6.6 and later trap `rdcycle` in user space, the timer is always readable there.
+    // SAFETY: reading the counters has no side effects.
+    #[cfg(target_os = "linux")]
+    unsafe {
+        std::arch::asm!("rdtime {cycles}", cycles = out(reg) cycles, options(nomem, nostack));
+    }
+    #[cfg(not(target_os = "linux"))]
+    unsafe {
+        std::arch::asm!("rdcycle {cycles}", cycles = out(reg) cycles, options(nomem, nostack));
+    }
+    Some(cycles)
+}
+
+/// Reads the cycle counter of the CPU, if there is one readable from user space.
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "riscv64")))]
+fn cycle_counter() -> Option<u64> {
+    None
+}
@@ -0,0 +1,34 @@
+use crate::entropy_pool::clock::Stopwatch;
+
+use super::EntropySource;
+
+/// Measures the time spent on a fixed amount of work.
+///
+/// Not one of the `default_sources`, which use the `JitterSource` instead. A single measurement
+/// provides little entropy, add this source explicitly for a cheap extra source of timing noise.
+pub struct TimingSource;
+
+impl EntropySource for TimingSource {
+    fn name(&self) -> &str {
+        "timing"
+    }
+
+    fn collect(&mut self, out: &mut Vec<u8>) {
+        let start = Stopwatch::start();
+        // multiply every element with every other element
+        let work: Vec<u8> = (1..=32).collect();
+        let mut matrix: Vec<u8> = Vec::with_capacity(work.len() * work.len());
+        for a in &work {
+            for b in &work {
+                matrix.push(a.wrapping_mul(*b));
+            }
+        }
+        let matrix_time_spend_in_nsec = start.elapsed().as_nanos();
+        out.extend_from_slice(&matrix_time_spend_in_nsec.to_le_bytes());
+        out.push(matrix[matrix_time_spend_in_nsec as usize % matrix.len()]);
+    }
+
+    fn estimated_entropy(&self) -> u32 {
+        4
+    }
+}
@@ -1,3 +1,16 @@
+#[cfg(unix)]
+use super::sources::OsRandomSource;
+use super::sources::{EntropySource, HardwareRandomSource, JitterSource, ProcessSource, SystemTimeSource};
+use super::chacha::ChaCha20;
+use super::health::HealthState;
+use super::sha256::{hmac_sha256, pbkdf2_hmac_sha256, sha256, Sha256};
+use super::zeroize::zeroize;
+use super::{EntropyPool, ReseedPolicy};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::EntropyPool;
@@ -95,3 +108,280 @@ mod tests {
         assert!(ok == 0);
     }
 }
+
+#[test]
+#[cfg(unix)]
+fn os_random_source_collects() {
+    let mut source = OsRandomSource::new();
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+    source.collect(&mut first);
+    source.collect(&mut second);
+    assert_eq!(first.len(), 32);
+    assert_eq!(second.len(), 32);
+    assert_ne!(first, second);
+    assert_eq!(source.estimated_entropy(), 256);
+    // the syscall is available on every supported Linux, /dev/urandom is only the fallback
+    #[cfg(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    assert!(!source.fell_back());
+}
+
+#[test]
+fn hardware_random_source_collects() {
+    let mut source = HardwareRandomSource::new();
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+    source.collect(&mut first);
+    source.collect(&mut second);
+    // the estimate follows the words actually read
+    assert_eq!(source.estimated_entropy() as usize, second.len() * 8);
+    if source.estimated_entropy() == 0 {
+        // no hardware random number generator available
+        assert!(first.is_empty());
+    } else {
+        assert_eq!(first.len(), 32);
+        assert_eq!(second.len(), 32);
+        assert_ne!(first, second);
+    }
+}
+
+#[test]
+fn jitter_source_collects() {
+    let mut source = JitterSource::new();
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+    source.collect(&mut first);
+    source.collect(&mut second);
+    assert_eq!(first.len(), 32);
+    assert_eq!(second.len(), 32);
+    assert_ne!(first, second);
+    assert!(source.estimated_entropy() > 0);
+}
+
+#[test]
+fn process_source_collects() {
+    let mut source = ProcessSource;
+    let mut collected = Vec::new();
+    source.collect(&mut collected);
+    assert!(collected.starts_with(&std::process::id().to_le_bytes()));
+    #[cfg(target_os = "linux")]
+    {
+        // pid, 3 addresses, thread path and 5 digests
+        assert!(collected.len() >= 4 + 3 * size_of::<usize>() + 5 * 8);
+    }
+}
+
+#[test]
+fn system_time_source_collects_raw_integers() {
+    let mut source = SystemTimeSource;
+    let mut first = Vec::new();
+    source.collect(&mut first);
+    // nanoseconds since the epoch and since the first call, plus the cycle counter if available
+    assert!(first.len() == 16 || first.len() == 24);
+    let since_epoch = u64::from_le_bytes(first[0..8].try_into().unwrap());
+    assert!(since_epoch > 1_600_000_000_000_000_000);
+    let mut second = Vec::new();
+    source.collect(&mut second);
+    assert_eq!(first.len(), second.len());
+    assert!(u64::from_le_bytes(second[8..16].try_into().unwrap()) > u64::from_le_bytes(first[8..16].try_into().unwrap()));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn file_system_salt_uses_full_width_metadata() {
+    use super::sources::{directory_salt, metadata_salt};
+    use std::os::unix::fs::MetadataExt;
+
+    let directory = std::env::temp_dir().join(format!("fortuna-file-system-{}", std::process::id()));
+    let nested = directory.join("a").join("b");
+    std::fs::create_dir_all(&nested).unwrap();
+    let file = nested.join("salt");
+    std::fs::write(&file, [0u8; 300]).unwrap();
+
+    // device ID, inode number, size, mtime, mtime nsec, ctime and ctime nsec
+    let salt = metadata_salt(&file);
+    let metadata = file.metadata().unwrap();
+    assert_eq!(salt.len(), 7 * 8);
+    assert_eq!(salt[0..8], metadata.dev().to_le_bytes());
+    assert_eq!(salt[8..16], metadata.ino().to_le_bytes());
+    assert_eq!(salt[16..24], 300u64.to_le_bytes());
+    assert_eq!(salt[24..32], metadata.mtime().to_le_bytes());
+    assert_eq!(salt[40..48], metadata.ctime().to_le_bytes());
+
+    // nest depth, then the metadata of the root directory
+    let salt = directory_salt(&nested);
+    assert_eq!(salt[0..8], (nested.ancestors().count() as u64).to_le_bytes());
+    assert_eq!(salt[8..], metadata_salt(std::path::Path::new("/")));
+    assert_ne!(directory_salt(&nested), directory_salt(&directory));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert!(metadata_salt(&file).is_empty());
+}
+
+#[test]
+fn repetition_count_cutoff() {
+    // with 8 bits per byte, 4 repetitions in a row are too many
+    assert!(HealthState::default().repetition_count(&[1, 2, 2, 2, 3], 8.0));
+    assert!(!HealthState::default().repetition_count(&[1, 2, 2, 2, 2, 3], 8.0));
+    // with 1 bit per byte, 21 repetitions in a row are too many
+    assert!(HealthState::default().repetition_count(&[0; 20], 1.0));
+    assert!(!HealthState::default().repetition_count(&[0; 21], 1.0));
+}
+
+#[test]
+fn adaptive_proportion_cutoff() {
+    let counting: Vec<u8> = (0..=255).cycle().take(1024).collect();
+    assert!(HealthState::default().adaptive_proportion(&counting, 8.0));
+    let mut mostly_zero = vec![0u8; 512];
+    for (i, byte) in mostly_zero.iter_mut().enumerate().skip(1).step_by(2) {
+        *byte = i as u8;
+    }
+    assert!(!HealthState::default().adaptive_proportion(&mostly_zero, 8.0));
+    assert!(HealthState::default().adaptive_proportion(&mostly_zero, 0.5));
+}
+
+#[test]
+fn health_tests_continue_across_collections() {
+    // a source stuck at the same 32 bytes passes every single collection
+    let stuck: Vec<u8> = (0..32).collect();
+    assert!(HealthState::default().repetition_count(&stuck, 8.0));
+    assert!(HealthState::default().adaptive_proportion(&stuck, 8.0));
+    // but not the window of 512 samples its collections add up to
+    let mut state = HealthState::default();
+    let passed: Vec<bool> = (0..16).map(|_| state.adaptive_proportion(&stuck, 8.0)).collect();
+    assert!(passed[0]);
+    assert!(!passed[15]);
+    // repetitions continue from the end of the previous collection
+    let mut state = HealthState::default();
+    assert!(state.repetition_count(&[1, 2, 2], 8.0));
+    assert!(!state.repetition_count(&[2, 2, 3], 8.0));
+}
+
+#[test]
+fn sha256_test_vectors() {
+    assert_eq!(hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    assert_eq!(hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    let two_blocks = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    assert_eq!(hex(&sha256(two_blocks)), "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+    // hashing in pieces gives the same digest
+    let mut hasher = Sha256::new();
+    for piece in two_blocks.chunks(7) {
+        hasher.update(piece);
+    }
+    assert_eq!(hasher.finalize(), sha256(two_blocks));
+}
+
+#[test]
+fn hmac_sha256_test_vectors() {
+    // RFC 4231, test cases 2 and 6
+    assert_eq!(
+        hex(&hmac_sha256(b"Jefe", &[b"what do ya want ", b"for nothing?"])),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+    assert_eq!(
+        hex(&hmac_sha256(&[0xaa; 131], &[b"Test Using Larger Than Block-Size Key - Hash Key First"])),
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+}
+
+#[test]
+fn pbkdf2_hmac_sha256_test_vectors() {
+    // RFC 7914, section 11
+    let mut out = [0u8; 64];
+    pbkdf2_hmac_sha256(b"passwd", b"salt", 1, &mut out);
+    assert_eq!(
+        hex(&out),
+        "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+         49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+    );
+    let mut out = [0u8; 32];
+    pbkdf2_hmac_sha256(b"password", b"salt", 4096, &mut out);
+    assert_eq!(hex(&out), "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a");
+}
+
+#[test]
+fn chacha20_test_vector() {
+    // RFC 8439, section 2.3.2
+    let key: Vec<u8> = (0..32).collect();
+    let nonce = [0, 0, 0, 9, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+    let mut chacha = ChaCha20::new(&key.try_into().unwrap(), &nonce, 1);
+    assert_eq!(
+        hex(&chacha.next_block()),
+        "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+         d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+    );
+}
+
+#[test]
+fn chacha20_simd_matches_portable() {
+    let key: [u8; 32] = core::array::from_fn(|i| i as u8 * 7);
+    // lengths that are no multiple of any backend, counters that wrap around within the output
+    for counter in [0, u32::MAX - 5] {
+        for length in [0, 63, 64, 300, 1_000, 4_097] {
+            let mut expected = vec![0u8; length];
+            ChaCha20::new(&key, &[3; 12], counter).fill_portable(&mut expected);
+            let mut actual = vec![0u8; length];
+            ChaCha20::new(&key, &[3; 12], counter).fill(&mut actual);
+            assert_eq!(actual, expected);
+
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+            for backend in super::chacha::simd::Backend::ALL.iter().filter(|backend| backend.is_supported()) {
+                let mut state = ChaCha20::new(&key, &[3; 12], counter);
+                let mut actual = vec![0u8; length];
+                let written = backend.fill(&mut state.state, &mut actual);
+                let group_length = backend.lanes() * super::chacha::BLOCK_LENGTH;
+                assert_eq!(written, length / group_length * group_length);
+                // the counter continues after the blocks the backend generated
+                state.fill_portable(&mut actual[written..]);
+                assert_eq!(actual, expected, "{backend:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn zeroize_overwrites_every_byte() {
+    let mut bytes = [0xa5u8; 100];
+    zeroize(&mut bytes);
+    assert_eq!(bytes, [0; 100]);
+}
+
+#[test]
+fn zeroize_consumed_clears_handed_out_bytes() {
+    let mut zeroing = EntropyPool::create_seeded_size_restricted(1_000, vec![1, 2, 3]);
+    let mut keeping = EntropyPool::create_seeded_size_restricted(1_000, vec![1, 2, 3]);
+    zeroing.set_zeroize_consumed(true);
+    // zeroing does not change the values handed out
+    assert_eq!(zeroing.get_random_byte(), keeping.get_random_byte());
+    assert_eq!(zeroing.get_random_bytes::<8>(), keeping.get_random_bytes::<8>());
+    let (mut zeroed, mut kept) = ([0u8; 100], [0u8; 100]);
+    zeroing.fill_bytes(&mut zeroed);
+    keeping.fill_bytes(&mut kept);
+    assert_eq!(zeroed, kept);
+    assert!(zeroing.pool[..109].iter().all(|byte| *byte == 0));
+    assert_eq!(zeroing.pool[109..], keeping.pool[109..]);
+
+    // a cycled pool hands out its bytes again, so they are kept
+    let mut cycling = EntropyPool::create_seeded_size_restricted(1_000, vec![1, 2, 3]);
+    cycling.set_zeroize_consumed(true);
+    cycling.set_reseed_policy(ReseedPolicy::Never);
+    cycling.fill_bytes(&mut [0; 100]);
+    assert_eq!(cycling.pool[..100], keeping.pool[..100]);
+}
+
+#[test]
+fn release_pool_frees_and_continues() {
+    let mut released = EntropyPool::create_seeded_size_restricted(1_000, vec![1, 2, 3]);
+    released.get_random_byte();
+    released.release_pool();
+    assert!(released.pool.is_empty());
+    assert_eq!(released.pool.capacity(), 0);
+
+    // the next pool follows, instead of the released one starting over
+    let mut exhausted = EntropyPool::create_seeded_size_restricted(1_000, vec![1, 2, 3]);
+    // the last byte of a pool is never handed out
+    exhausted.fill_bytes(&mut [0; 999]);
+    for _ in 0..2_000 {
+        assert_eq!(released.get_random_byte(), exhausted.get_random_byte());
+    }
+}
@@ -0,0 +1,24 @@
+//! Overwriting secrets with zeros, in a way the compiler does not optimize away.
+
+use core::sync::atomic::{compiler_fence, Ordering};
+
+/// Overwrites `bytes` with zeros.
+///
+/// The writes are volatile, so they are kept even if `bytes` is never read again, e.g. because it
+/// is about to be freed.
+pub fn zeroize(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned and exclusive reference
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Overwrites `words` with zeros, like `zeroize` does for bytes.
+pub fn zeroize_words(words: &mut [u32]) {
+    for word in words.iter_mut() {
+        // SAFETY: `word` is a valid, aligned and exclusive reference
+        unsafe { core::ptr::write_volatile(word, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
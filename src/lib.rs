@@ -19,7 +19,9 @@ fortuna = { git = "https://github.com/Xqhare/fortuna" }
 Then run `cargo update` to pull the latest version.
 
 ### Example
-As `Fortuna` is quite small in scope, every function is listed in the example below.
+Every generation method is listed in the example below.
+The other constructors (e.g. `Fortuna::new_lazy`, `Fortuna::new_replayable` and `FortunaInline`) and the pool controls
+(`Fortuna::warm_up`, `Fortuna::release_pool` and `Fortuna::set_zeroize_consumed`) are documented on the types.
 
 ```rust
 use fortuna::Fortuna;
@@ -64,6 +66,9 @@ fn main() {
     let random_with_ceiling: usize = fortuna.random_with_ceiling(100);
     let random_with_floor: usize = fortuna.random_with_floor(100);
 
+    let mut random_bytes = [0u8; 32];
+    fortuna.fill_bytes(&mut random_bytes);
+
     println!("Generated random u8: {}", random_u8);
     println!("Generated random u16: {}", random_u16);
     println!("Generated random u32: {}", random_u32);
@@ -99,24 +104,55 @@ fn main() {
 
     println!("Generated random with ceiling: {}", random_with_ceiling);
     println!("Generated random with floor: {}", random_with_floor);
+
+    println!("Generated random bytes: {:?}", random_bytes);
 }
 ```
 
 ## Entropy sources
 Fortuna uses the following entropy sources:
-- System time
+- Operating system randomness (if available)
+    - `getrandom(2)` on Linux on x86_64 and aarch64, falling back to `/dev/urandom` on any unix
+- Hardware random number generator (if available)
+    - `RDSEED` or `RDRAND` on x86_64, `RNDR` on aarch64
+- Time
+    - System time and monotonic time as raw nanoseconds
+    - CPU cycle counter (if available): `RDTSC` on x86_64, `CNTVCT_EL0` on aarch64, `RDCYCLE` on riscv64 (`RDTIME` on Linux)
+- Process state
+    - Process ID and the addresses of the stack, heap and code (ASLR)
+    - On Linux: thread ID, `/proc/self/stat`, `/proc/interrupts`, `/proc/loadavg`, `/proc/stat` and the boot ID
 - File system properties (if available)
-    - Main disk device ID and inode number
     - Directory nest depth to root
-    - Current working directory properties
+    - Device ID, inode number, size, modification and status change time of the root directory, the current working directory and the executable
 - Code execution times
+    - Time spent collecting from every source
+    - CPU jitter, measured over many memory-access and branch-heavy loops
 - CPU features
 
+Sources can be replaced or extended, see the `sources` module.
+
 */
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod entropy_pool;
+mod random;
 
-use crate::entropy_pool::EntropyPool;
-use std::ops::{Add, Sub};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::entropy_pool::{
+    seed_from_bytes, seed_from_u64, stretch_passphrase, EntropyPool, InlinePool, PASSPHRASE_ROUNDS,
+};
+
+pub use crate::entropy_pool::sources;
+pub use crate::entropy_pool::{
+    EntropyReport, HealthError, DEFAULT_POOL_SIZE, RegenerationEvent, RegenerationReason, ReseedPolicy, SourceHealth, SourceReport,
+};
+
+use crate::sources::{CallbackSource, EntropySource, HostEntropySource};
 
 /// `Fortuna` is a struct that contains a pool of pseudo-random bytes.
 /// The entropy pool will regenerate itself if it is empty.
@@ -125,6 +161,9 @@ use std::ops::{Add, Sub};
 ///
 /// `Fortuna` does not panic.
 ///
+/// When dropped, `Fortuna` overwrites its entropy pool and seed with zeros, so they do not end
+/// up in a core dump or in reused memory.
+///
 /// Generating or regenerating the entropy pool is an expensive operation, so it is recommended to
 /// keep the pool size on the larger side if you are constructing `Fortuna` by using `create_size_restricted`.
 ///
@@ -220,7 +259,8 @@ impl Fortuna {
     /// sure to use as few instances as possible.
     /// The pool will regenerate itself if it is empty.
     ///
-    /// Use `create_size_restricted` to create a new instance with a size restricted pool.
+    /// The pool holds `DEFAULT_POOL_SIZE` bytes. Use `create_size_restricted` to create a new
+    /// instance with a different pool size.
     ///
     /// ## Example
     /// ```
@@ -234,12 +274,60 @@ impl Fortuna {
     ///   println!("Generated random bool: {}", random_bool);
     /// }
     /// ```
+    #[cfg(feature = "std")]
     pub fn new() -> Self {
         Self {
             entropy_pool: EntropyPool::new(),
         }
     }
 
+    /// Creates a new `Fortuna` instance that generates its entropy pool on first use.
+    ///
+    /// Construction neither collects entropy nor allocates the pool, so it costs next to nothing.
+    /// The pool is generated by the first request for a random value, or by `warm_up`, which lets
+    /// you pay for it off the hot path. Otherwise the instance is the same as one created with
+    /// `new`.
+    ///
+    /// ## Example
+    /// ```
+    /// use fortuna::Fortuna;
+    ///
+    /// fn main() {
+    ///   let mut fortuna = Fortuna::new_lazy();
+    ///   // generate the pool now, instead of on the first request
+    ///   fortuna.warm_up();
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new_lazy() -> Self {
+        Self::create_lazy_with_sources(sources::default_sources())
+    }
+
+    /// Creates a new `Fortuna` instance, failing if any entropy source fails its health tests.
+    ///
+    /// Every source is tested for looking stuck, e.g. a timer returning coarse values inside a
+    /// container. `new` leaves failing sources out and relies on the remaining ones, `try_new`
+    /// returns an error instead.
+    /// Sources failing during later regenerations are always left out.
+    ///
+    /// ## Example
+    /// ```
+    /// use fortuna::Fortuna;
+    ///
+    /// fn main() {
+    ///   match Fortuna::try_new() {
+    ///     Ok(mut fortuna) => println!("Generated random u8: {}", fortuna.random_u8()),
+    ///     Err(err) => println!("{}", err),
+    ///   }
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_new() -> Result<Self, HealthError> {
+        Self::try_create_with_sources(sources::default_sources())
+    }
+
     /// Creates a new `Fortuna` instance with a restricted pool size.
     /// This is useful if you are only generating a small number of random values, or operating
     /// with little memory.
@@ -265,703 +353,786 @@ impl Fortuna {
     ///   println!("Generated random bool: {}", random_bool);
     /// }
     /// ```    
+    #[cfg(feature = "std")]
     pub fn create_size_restricted(initial_pool_size: usize) -> Self {
         Self {
             entropy_pool: EntropyPool::create_size_restricted(initial_pool_size),
         }
     }
 
-    /// Creates a new `Fortuna` instance with a seeded entropy pool.
-    /// This is useful if you want to generate the same random values every time.
-    /// The seed should be chosen randomly, and kept secret.
-    /// The pool will regenerate itself if it is empty.
+    /// Creates a new `Fortuna` instance using the given entropy sources instead of the default ones.
     ///
-    /// `Fortuna` contains a pool of entropy. Generating the pool is resource intensive, so make
-    /// sure to use as few instances as possible.
-    /// The pool will regenerate itself if it is empty.
+    /// The sources are used every time the entropy pool is generated.
+    /// Use `sources::default_sources` to start from the built-in sources, and implement
+    /// `sources::EntropySource` to add your own.
     ///
     /// ## Parameters:
-    /// - `seed`: The seed to use for the entropy pool.
+    /// - `sources`: The entropy sources to use.
     ///
     /// ## Example
     /// ```
     /// use fortuna::Fortuna;
+    /// use fortuna::sources::{SystemTimeSource, TimingSource};
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::create_seeded(vec![1, 2, 3, 4]);
+    ///   let mut fortuna = Fortuna::create_with_sources(vec![Box::new(SystemTimeSource), Box::new(TimingSource)]);
     ///   let random_number: u8 = fortuna.random_u8();
-    ///   let random_bool: bool = fortuna.random_bool();
     ///   println!("Generated random u8: {}", random_number);
-    ///   println!("Generated random bool: {}", random_bool);
     /// }
     /// ```
-    pub fn create_seeded(seed: Vec<u8>) -> Self {
+    pub fn create_with_sources(sources: Vec<Box<dyn EntropySource>>) -> Self {
         Self {
-            entropy_pool: EntropyPool::create_seeded(seed),
+            entropy_pool: EntropyPool::create_with_sources(sources),
         }
     }
 
-    /// Creates a new `Fortuna` instance with a seeded and size restricted entropy pool.
-    /// This is useful if you want to generate the same random values every time.
-    /// The seed should be chosen randomly, and kept secret.
-    /// The pool will regenerate itself if it is empty.
+    /// Creates a new `Fortuna` instance using the given entropy sources, failing if any source
+    /// fails its health tests.
     ///
-    /// `Fortuna` contains a pool of entropy. Generating the pool is resource intensive, so make
-    /// sure to use as few instances as possible.
-    /// The pool will regenerate itself if it is empty.
+    /// See `try_new` and `create_with_sources` for details.
     ///
     /// ## Parameters:
-    /// - `seed`: The seed to use for the entropy pool.
-    /// - `initial_pool_size`: The initial size of the pool.
+    /// - `sources`: The entropy sources to use.
     ///
     /// ## Example
     /// ```
     /// use fortuna::Fortuna;
+    /// use fortuna::sources::default_sources;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4], 1_000);
+    ///   let fortuna = Fortuna::try_create_with_sources(default_sources());
+    ///   assert!(fortuna.is_ok());
+    /// }
+    /// ```
+    pub fn try_create_with_sources(sources: Vec<Box<dyn EntropySource>>) -> Result<Self, HealthError> {
+        Ok(Self {
+            entropy_pool: EntropyPool::try_create_with_sources(sources)?,
+        })
+    }
+
+    /// Creates a new `Fortuna` instance using the given entropy sources, generating its entropy
+    /// pool on first use.
+    ///
+    /// See `new_lazy` and `create_with_sources` for details.
+    ///
+    /// ## Parameters:
+    /// - `sources`: The entropy sources to use.
+    ///
+    /// ## Example
+    /// ```
+    /// use fortuna::Fortuna;
+    /// use fortuna::sources::{SystemTimeSource, TimingSource};
+    ///
+    /// fn main() {
+    ///   let mut fortuna = Fortuna::create_lazy_with_sources(vec![Box::new(SystemTimeSource), Box::new(TimingSource)]);
     ///   let random_number: u8 = fortuna.random_u8();
-    ///   let random_bool: bool = fortuna.random_bool();
     ///   println!("Generated random u8: {}", random_number);
-    ///   println!("Generated random bool: {}", random_bool);
     /// }
     /// ```
-    pub fn create_seeded_size_restricted(seed: Vec<u8>, initial_pool_size: usize) -> Self {
+    pub fn create_lazy_with_sources(sources: Vec<Box<dyn EntropySource>>) -> Self {
         Self {
-            entropy_pool: EntropyPool::create_seeded_size_restricted(initial_pool_size, seed),
+            entropy_pool: EntropyPool::create_lazy_with_sources(DEFAULT_POOL_SIZE, sources),
         }
     }
 
-    /// Generates a pseudo-random `u8`.
+    /// Creates a new `Fortuna` instance with a restricted pool size, using the given entropy
+    /// sources instead of the default ones.
+    ///
+    /// See `create_size_restricted` and `create_with_sources` for details.
     ///
-    /// ## Example:
+    /// ## Parameters:
+    /// - `initial_pool_size`: The initial size of the pool.
+    /// - `sources`: The entropy sources to use.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
+    /// use fortuna::sources::default_sources;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
+    ///   let mut sources = default_sources();
+    ///   sources.retain(|source| source.name() != "file system");
+    ///   let mut fortuna = Fortuna::create_size_restricted_with_sources(1_000, sources);
     ///   let random_number: u8 = fortuna.random_u8();
     ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_u8(&mut self) -> u8 {
-        self.entropy_pool.get_random_byte()
+    pub fn create_size_restricted_with_sources(initial_pool_size: usize, sources: Vec<Box<dyn EntropySource>>) -> Self {
+        Self {
+            entropy_pool: EntropyPool::create_size_restricted_with_sources(initial_pool_size, sources),
+        }
     }
 
-    /// Generates a pseudo-random `u16`
+    /// Creates a new `Fortuna` instance using `callback` as its only entropy source.
+    ///
+    /// `callback` fills the given buffer with random bytes, e.g. read from the hardware random
+    /// number generator of a microcontroller. It is called on construction and on every
+    /// regeneration. This is how unseeded instances are created without the `std` feature.
+    ///
+    /// ## Parameters:
+    /// - `callback`: Fills the given buffer with random bytes.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
+    /// fn read_hardware_rng(buffer: &mut [u8]) {
+    ///   // read the hardware random number generator here
+    ///   # buffer.copy_from_slice(&[7; 32]);
+    /// }
+    ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: u16 = fortuna.random_u16();
-    ///   println!("Generated random u16: {}", random_number);
+    ///   let mut fortuna = Fortuna::from_entropy_callback(read_hardware_rng);
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_u16(&mut self) -> u16 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        u16::from_le_bytes(rng)
+    pub fn from_entropy_callback<F>(callback: F) -> Self
+    where
+        F: FnMut(&mut [u8]) + Send + Sync + 'static,
+    {
+        Self::create_with_sources(vec![Box::new(CallbackSource::new(callback))])
     }
 
-    /// Generates a pseudo-random `u32`
+    /// Creates a new `Fortuna` instance using entropy provided by the host, in addition to the
+    /// default entropy sources (if the `std` feature is enabled).
+    ///
+    /// This is meant for targets with little entropy of their own, like `wasm32-unknown-unknown`,
+    /// where there is no clock, no operating system and no file system. The host passes in
+    /// random bytes, e.g. from `crypto.getRandomValues`. At least 32 bytes should be provided.
+    ///
+    /// The entropy is used for the first pool, and carried over into every pool after it.
+    /// Unlike a seed, it is not kept and can not be replayed.
+    ///
+    /// ## Parameters:
+    /// - `entropy`: Random bytes provided by the host.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: u32 = fortuna.random_u32();
-    ///   println!("Generated random u32: {}", random_number);
+    ///   // e.g. passed in from JavaScript
+    ///   let host_entropy = [42u8; 32];
+    ///   let mut fortuna = Fortuna::from_host_entropy(&host_entropy);
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_u32(&mut self) -> u32 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        u32::from_le_bytes(rng)
+    pub fn from_host_entropy(entropy: &[u8]) -> Self {
+        let host: Box<dyn EntropySource> = Box::new(HostEntropySource::new(entropy));
+        #[cfg(feature = "std")]
+        let sources = sources::default_sources().into_iter().chain([host]).collect();
+        #[cfg(not(feature = "std"))]
+        let sources = vec![host];
+        Self::create_with_sources(sources)
     }
 
-    /// Generates a pseudo-random `u64`
+    /// Creates a new `Fortuna` instance with a seeded entropy pool.
+    /// This is useful if you want to generate the same random values every time.
+    /// The seed should be chosen randomly, and kept secret.
+    /// The pool will regenerate itself if it is empty.
+    ///
+    /// `Fortuna` contains a pool of entropy. Generating the pool is resource intensive, so make
+    /// sure to use as few instances as possible.
+    /// The pool will regenerate itself if it is empty.
     ///
-    /// This function needs a 64bit system for obvious reasons.
+    /// The pool holds `DEFAULT_POOL_SIZE` bytes, no matter the length of the seed. Use
+    /// `create_seeded_size_restricted` to choose a different pool size.
     ///
-    /// ## Example:
+    /// ## Parameters:
+    /// - `seed`: The seed to use for the entropy pool.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: u64 = fortuna.random_u64();
-    ///   println!("Generated random u64: {}", random_number);
+    ///   let mut fortuna = Fortuna::create_seeded(vec![1, 2, 3, 4]);
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   let random_bool: bool = fortuna.random_bool();
+    ///   println!("Generated random u8: {}", random_number);
+    ///   println!("Generated random bool: {}", random_bool);
     /// }
     /// ```
-    pub fn random_u64(&mut self) -> u64 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        u64::from_le_bytes(rng)
+    pub fn create_seeded(seed: Vec<u8>) -> Self {
+        Self {
+            entropy_pool: EntropyPool::create_seeded(seed),
+        }
     }
 
-    /// Generates a pseudo-random `i8`
+    /// Creates a new `Fortuna` instance with a seeded and size restricted entropy pool.
+    /// This is useful if you want to generate the same random values every time.
+    /// The seed should be chosen randomly, and kept secret.
+    /// The pool will regenerate itself if it is empty.
     ///
-    /// ## Example:
+    /// `Fortuna` contains a pool of entropy. Generating the pool is resource intensive, so make
+    /// sure to use as few instances as possible.
+    /// The pool will regenerate itself if it is empty.
+    ///
+    /// ## Parameters:
+    /// - `seed`: The seed to use for the entropy pool.
+    /// - `initial_pool_size`: The initial size of the pool.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: i8 = fortuna.random_i8();
-    ///   println!("Generated random i8: {}", random_number);
+    ///   let mut fortuna = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4], 1_000);
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   let random_bool: bool = fortuna.random_bool();
+    ///   println!("Generated random u8: {}", random_number);
+    ///   println!("Generated random bool: {}", random_bool);
     /// }
     /// ```
-    pub fn random_i8(&mut self) -> i8 {
-        let rng = [self.entropy_pool.get_random_byte()];
-        i8::from_le_bytes(rng)
+    pub fn create_seeded_size_restricted(seed: Vec<u8>, initial_pool_size: usize) -> Self {
+        Self {
+            entropy_pool: EntropyPool::create_seeded_size_restricted(initial_pool_size, seed),
+        }
     }
 
-    /// Generates a pseudo-random `i16`
+    /// Creates a new `Fortuna` instance seeded with a `u64`.
+    ///
+    /// The number is expanded into a full length seed first, so even small numbers like `0` or
+    /// `1` produce unrelated streams of values.
+    /// Otherwise this behaves like `create_seeded`.
+    ///
+    /// ## Parameters:
+    /// - `seed`: The number to derive the seed from.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: i16 = fortuna.random_i16();
-    ///   println!("Generated random i16: {}", random_number);
+    ///   let mut fortuna = Fortuna::from_seed_u64(42);
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_i16(&mut self) -> i16 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        i16::from_le_bytes(rng)
+    pub fn from_seed_u64(seed: u64) -> Self {
+        Self::create_seeded(seed_from_u64(seed))
     }
 
-    /// Generates a pseudo-random `i32`
+    /// Creates a new `Fortuna` instance seeded with a string.
+    ///
+    /// The string is compressed or expanded into a full length seed first, so strings of any
+    /// length produce streams of the same quality.
+    /// Otherwise this behaves like `create_seeded`.
     ///
-    /// ## Example:
+    /// ## Parameters:
+    /// - `seed`: The string to derive the seed from.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: i32 = fortuna.random_i32();
-    ///   println!("Generated random i32: {}", random_number);
+    ///   let mut fortuna = Fortuna::from_seed_str("my favourite seed");
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_i32(&mut self) -> i32 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        i32::from_le_bytes(rng)
+    pub fn from_seed_str(seed: &str) -> Self {
+        Self::create_seeded(seed_from_bytes(seed.as_bytes()))
     }
 
-    /// Generates a pseudo-random `i64`
+    /// Creates a new `Fortuna` instance seeded with a passphrase.
+    ///
+    /// Like `from_seed_str`, but the seed is derived from the passphrase with PBKDF2-HMAC-SHA-256 in
+    /// `PASSPHRASE_ROUNDS` (600,000) rounds. This makes deriving the seed deliberately slow, so
+    /// guessing the passphrase from the generated values is expensive.
+    /// Otherwise this behaves like `create_seeded`.
+    ///
+    /// ## Parameters:
+    /// - `passphrase`: The passphrase to derive the seed from.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: i64 = fortuna.random_i64();
-    ///   println!("Generated random i64: {}", random_number);
+    ///   let mut fortuna = Fortuna::from_passphrase("correct horse battery staple");
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_i64(&mut self) -> i64 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        i64::from_le_bytes(rng)
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Self::create_seeded(stretch_passphrase(passphrase.as_bytes(), PASSPHRASE_ROUNDS))
     }
 
-    /// Generates a pseudo-random `f32`
+    /// Creates a new `Fortuna` instance from system entropy that can be replayed later.
+    ///
+    /// Unlike `new`, the system entropy is only used to derive a compact seed, which is then used
+    /// like `create_seeded`. The seed can be read with `seed` and stored, so the exact values
+    /// generated can be reproduced later, e.g. to replay an incident in a debugger.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: f32 = fortuna.random_f32();
-    ///   println!("Generated random f32: {}", random_number);
-    /// }
-    /// ```
-    pub fn random_f32(&mut self) -> f32 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        let out = f32::from_le_bytes(rng);
-        if out.is_nan() {
-            self.random_f32()
-        } else {
-            out
+    ///   let mut fortuna = Fortuna::new_replayable();
+    ///   let seed = fortuna.seed().unwrap().to_vec();
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///
+    ///   let mut replay = Fortuna::create_seeded(seed);
+    ///   assert_eq!(random_number, replay.random_u8());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new_replayable() -> Self {
+        Self {
+            entropy_pool: EntropyPool::create_replayable(),
         }
     }
 
-    /// Generates a pseudo-random `f64`
+    /// Creates a new `Fortuna` instance seeded from the environment variable `variable`, or from
+    /// system entropy if it is not set.
+    ///
+    /// This is useful to make randomized tests reproducible: If a run fails, rerun it with the
+    /// variable set to the printed seed and the exact same values are generated again.
     ///
-    /// ## Example:
+    /// Leading and trailing whitespace of the variable is ignored.
+    /// - If the variable contains a number, it is used like `from_seed_u64`.
+    /// - If the variable contains anything else, it is used like `from_seed_str`.
+    /// - If the variable is not set, a seed is drawn from system entropy and printed to stderr
+    ///   as `variable=seed`, then used like `from_seed_u64`.
+    /// - If the variable is not valid unicode, its raw bytes are used like `from_seed_str` and a
+    ///   note is printed to stderr.
+    ///
+    /// ## Parameters:
+    /// - `variable`: The name of the environment variable to read the seed from.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_number: f64 = fortuna.random_f64();
-    ///   println!("Generated random f64: {}", random_number);
-    /// }
-    /// ```
-    pub fn random_f64(&mut self) -> f64 {
-        let rng = [
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-            self.entropy_pool.get_random_byte(),
-        ];
-        let out = f64::from_le_bytes(rng);
-        if out.is_nan() {
-            self.random_f64()
-        } else {
-            out
+    ///   // Rerun with `FORTUNA_SEED=<printed seed>` to get the same values again
+    ///   let mut fortuna = Fortuna::from_env_or_entropy("FORTUNA_SEED");
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_env_or_entropy(variable: &str) -> Self {
+        match std::env::var(variable) {
+            Ok(value) => {
+                let value = value.trim();
+                match value.parse::<u64>() {
+                    Ok(seed) => Self::from_seed_u64(seed),
+                    Err(_) => Self::from_seed_str(value),
+                }
+            }
+            Err(std::env::VarError::NotPresent) => {
+                let seed = Self::create_size_restricted(16).random_u64();
+                eprintln!("fortuna: {variable} not set, seeding with {variable}={seed}");
+                Self::from_seed_u64(seed)
+            }
+            Err(std::env::VarError::NotUnicode(value)) => {
+                eprintln!("fortuna: {variable} is not valid unicode, seeding with its raw bytes");
+                Self::create_seeded(seed_from_bytes(value.as_encoded_bytes()))
+            }
         }
     }
 
-    /// Generates a pseudo-random latin character.
+    /// Sets an observer that is called every time the entropy pool is regenerated.
+    /// Any previously set observer is replaced.
     ///
-    /// ## Arguments
+    /// The observer receives a `RegenerationEvent` containing the reason for the regeneration,
+    /// the size of the new pool, the seeded iteration (if seeded) and the time spent regenerating.
+    /// This is useful for logging or metering how often the expensive regeneration happens.
     ///
-    /// * `uppercase` - `true` for upper case, `false` for lower case
+    /// ## Parameters:
+    /// - `hook`: The observer to call after every regeneration.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_char_uppercase: char = fortuna.random_latin_char(true);
-    ///   println!("Generated random uppercase char: {}", random_char_uppercase);
-    ///   let random_char_lowercase: char = fortuna.random_latin_char(false);
-    ///   println!("Generated random lowercase char: {}", random_char_lowercase);
-    /// }
-    /// ```
-    pub fn random_latin_char(&mut self, uppercase: bool) -> char {
-        let chars = [
-            'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
-            'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
-        ];
-        let chosen_char = chars[self.random_index(chars.len())];
-        if uppercase {
-            chosen_char.to_ascii_uppercase()
-        } else {
-            chosen_char
-        }
+    ///   let mut fortuna = Fortuna::create_size_restricted(1_000);
+    ///   fortuna.on_regenerate(|event| {
+    ///     println!("Regenerated {} bytes in {:?} ({:?})", event.pool_size, event.elapsed, event.reason);
+    ///   });
+    ///   for _ in 0..2_000 {
+    ///     fortuna.random_u8();
+    ///   }
+    /// }
+    /// ```
+    pub fn on_regenerate<F>(&mut self, hook: F)
+    where
+        F: FnMut(RegenerationEvent) + Send + Sync + 'static,
+    {
+        self.entropy_pool.set_on_regenerate(Some(Box::new(hook)));
     }
 
-    /// Generates a pseudo-random ASCII character.
+    /// Removes the observer set with `on_regenerate`, if any.
     ///
-    /// The allowed range of ASCII in HEX is 21 - FF with the exceptions 7F (DEL), 81 + 8D + 8F +
-    /// 90 + 9D (Unused), A0 (Non-breaking space) and AD (Soft hyphen)
-    ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
     ///   let mut fortuna = Fortuna::new();
-    ///   let random_char: char = fortuna.random_ascii_char();
-    ///   println!("Generated random char: {}", random_char);
-    /// }
-    /// ```
-    pub fn random_ascii_char(&mut self) -> char {
-        let ran_u8 = self.entropy_pool.get_random_byte();
-        if is_valid_ascii(ran_u8) {
-            return char::from_u32(ran_u8 as u32).expect("Valid ASCII character");
-        } else {
-            let try1 = ran_u8.saturating_add(self.entropy_pool.get_random_byte());
-            if is_valid_ascii(try1) {
-                return char::from_u32(try1 as u32).expect("Valid ASCII character");
-            }
-            let try2 = ran_u8.saturating_sub(self.entropy_pool.get_random_byte());
-            if is_valid_ascii(try2) {
-                return char::from_u32(try2 as u32).expect("Valid ASCII character");
-            }
-            let try3 = ran_u8.saturating_add(self.entropy_pool.get_random_byte());
-            if is_valid_ascii(try3) {
-                return char::from_u32(try3 as u32).expect("Valid ASCII character");
-            }
-            let try4 = ran_u8.saturating_sub(self.entropy_pool.get_random_byte());
-            if is_valid_ascii(try4) {
-                return char::from_u32(try4 as u32).expect("Valid ASCII character");
-            }
-            return self.random_ascii_char();
-        }
+    ///   fortuna.on_regenerate(|event| println!("{:?}", event));
+    ///   fortuna.clear_on_regenerate();
+    /// }
+    /// ```
+    pub fn clear_on_regenerate(&mut self) {
+        self.entropy_pool.set_on_regenerate(None);
     }
 
-    /// Generates a pseudo-random boolean.
+    /// Returns the seed of a seeded `Fortuna` instance, or `None` if it uses system entropy.
+    ///
+    /// For instances created with `from_seed_u64`, `from_seed_str`, `from_passphrase` or
+    /// `new_replayable` this is the derived seed.
+    /// Passing it to `create_seeded` (or `create_seeded_size_restricted` with the same pool size)
+    /// replays the same values from the start. Data added with `reseed` or `add_entropy` is not
+    /// part of the seed and has to be added again at the same points to replay them.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///   let mut fortuna = Fortuna::new();
-    ///   let random_bool: bool = fortuna.random_bool();
-    ///   println!("Generated random bool: {}", random_bool);
+    ///   let fortuna = Fortuna::create_seeded(vec![1, 2, 3, 4]);
+    ///   assert_eq!(fortuna.seed(), Some(&[1, 2, 3, 4][..]));
+    ///   let unseeded = Fortuna::new();
+    ///   assert_eq!(unseeded.seed(), None);
     /// }
     /// ```
-    pub fn random_bool(&mut self) -> bool {
-        let rng = self.entropy_pool.get_random_byte();
-        if rng % 2 == 0 {
-            return false;
-        } else {
-            return true;
-        }
+    pub fn seed(&self) -> Option<&[u8]> {
+        self.entropy_pool.seed()
     }
 
-    /// Call with the start and end of the range (both `usize`).
-    /// The range is inclusive on both ends.
+    /// Returns the results of the health tests of every entropy source, from the last time the
+    /// entropy pool was generated.
     ///
-    /// ## Example:
+    /// Sources that failed (see `SourceHealth::passed`) were left out of the pool.
+    /// Seeded instances do not use entropy sources, so the list is empty.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_range(0, 100);
-    ///     println!("Chosen element {chosen_element}, in range 0-100");
+    ///   let fortuna = Fortuna::new();
+    ///   for source in fortuna.health_report() {
+    ///     println!("{}: {}", source.name, if source.passed() { "ok" } else { "stuck" });
+    ///   }
     /// }
     /// ```
-    pub fn random_from_range(&mut self, start: usize, end: usize) -> usize {
-        if start < end {
-            let range_size = (end).saturating_sub(start).saturating_add(1);
-            let rng = self.random_u32();
-            let random_index = rng as usize % range_size;
-            start.saturating_add(random_index)
-        } else if start == end {
-            start
-        } else {
-            self.random_from_range(end, start)
-        }
+    pub fn health_report(&self) -> Vec<SourceHealth> {
+        self.entropy_pool.report().sources.iter().map(|source| source.health.clone()).collect()
     }
 
-    /// Call with the start and end of the range (both `u32`).
-    /// The range is inclusive on both ends.
+    /// Returns a report of the entropy `Fortuna` was generated from, from the last time the
+    /// entropy pool was generated.
+    ///
+    /// The report lists every entropy source used, the bytes it contributed, whether it fell back
+    /// to a pre-generated value and a rough heuristic of its min-entropy, which is meaningless for
+    /// the small number of bytes a source collects. `EntropyReport::total_entropy` sums up the
+    /// estimates.
+    /// Seeded instances do not use entropy sources, so the report is empty. So is the report of
+    /// lazily created instances, until their pool is generated.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_u32_range(0, 100);
-    ///     println!("Chosen element {chosen_element}, in range 0-100");
+    ///   let fortuna = Fortuna::new();
+    ///   let report = fortuna.entropy_report();
+    ///   for source in &report.sources {
+    ///     println!(
+    ///       "{}: {} bytes, fell back: {}, estimated {} bits, heuristic {:.1} bits",
+    ///       source.name, source.bytes, source.fell_back, source.estimated_entropy, source.min_entropy
+    ///     );
+    ///   }
+    ///   println!("Total: {:.1} bits", report.total_entropy());
     /// }
     /// ```
-    pub fn random_from_u32_range(&mut self, start: u32, end: u32) -> u32 {
-        if start < end {
-            let range_size = (end).saturating_sub(start).saturating_add(1);
-            let rng = self.random_u32();
-            let random_index = rng % range_size;
-            start.saturating_add(random_index)
-        } else if start == end {
-            start
-        } else {
-            self.random_from_u32_range(end, start)
-        }
+    pub fn entropy_report(&self) -> &EntropyReport {
+        self.entropy_pool.report()
     }
 
-    /// Call with the start and end of the range (both `u64`).
-    /// The range is inclusive on both ends.
+    /// Generates the entropy pool of a lazily created instance (`new_lazy`) or one whose pool has
+    /// been released (`release_pool`), so the first request for a random value does not have to.
     ///
-    /// This function needs a 64bit system for obvious reasons.
+    /// Does nothing if the pool has already been generated.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_u64_range(0, 100);
-    ///     println!("Chosen element {chosen_element}, in range 0-100");
+    ///   let mut fortuna = Fortuna::new_lazy();
+    ///   fortuna.warm_up();
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_from_u64_range(&mut self, start: u64, end: u64) -> u64 {
-        if start < end {
-            let range_size = (end - start).saturating_add(1);
-            let rng = self.random_u64();
-            let random_index = rng % range_size;
-            start.saturating_add(random_index)
-        } else if start == end {
-            start
-        } else {
-            self.random_from_u64_range(end, start)
-        }
+    pub fn warm_up(&mut self) {
+        self.entropy_pool.warm_up();
     }
 
-    /// Call with the start and end of the range (both `f32`).
-    /// The range is inclusive on start, and never quite reaches end.
+    /// Sets whether bytes are overwritten with zeros in the entropy pool once they have been handed
+    /// out, so values generated earlier can not be read from memory later, e.g. from a core dump.
+    ///
+    /// Off by default, as it costs a little time per value. Has no effect with `ReseedPolicy::Never`,
+    /// as the pool is cycled through again instead of being regenerated.
     ///
-    /// ## Example:
+    /// ## Parameters:
+    /// - `zeroize_consumed`: Whether to zero bytes once they have been handed out.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_f32_range(0.1, 100.1);
-    ///     println!("Chosen element {chosen_element}, in range 0.1-100.1");
-    /// }
-    /// ```
-    pub fn random_from_f32_range(&mut self, start: f32, end: f32) -> f32 {
-        if start < end {
-            // I still believe this to have an off by one error, however it is infinity small
-            // because of f32.
-            // As further reading did not help in the slightes but confirm that floating point
-            // numbers are weird I will have to live with it. It seems to grow towards end, and
-            // never reaching it. I now suspect maths shinanigans.
-            let range_size = end.sub(start); //.add(1.0);
-            let rng = self.random_f32();
-            if rng.is_sign_positive() {
-                let random_index = rng % range_size;
-                start.add(random_index)
-            } else {
-                let random_index = (rng * -1.0) % range_size;
-                start.add(random_index)
-            }
-        } else if start == end {
-            start
-        } else {
-            self.random_from_f32_range(end, start)
-        }
+    ///   let mut fortuna = Fortuna::new();
+    ///   fortuna.set_zeroize_consumed(true);
+    ///   let secret: u64 = fortuna.random_u64();
+    ///   println!("Generated random u64: {}", secret);
+    /// }
+    /// ```
+    pub fn set_zeroize_consumed(&mut self, zeroize_consumed: bool) {
+        self.entropy_pool.set_zeroize_consumed(zeroize_consumed);
     }
 
-    /// Call with the start and end of the range (both `f64`).
-    /// The range is inclusive on start, and never quite reaches end.
+    /// Overwrites the entropy pool with zeros and frees its memory.
     ///
-    /// This function needs a 64bit system for obvious reasons.
+    /// Call it when the instance will be idle for a while. The next request for a random value
+    /// regenerates the pool, like `warm_up` does. Seeded instances continue their stream with the
+    /// next pool, the rest of the released pool is skipped.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_f64_range(-100.1, 100.1);
-    ///     println!("Chosen element {chosen_element}, in range -100.1 - 100.1");
+    ///   let mut fortuna = Fortuna::new();
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   // nothing to generate for a while
+    ///   fortuna.release_pool();
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_from_f64_range(&mut self, start: f64, end: f64) -> f64 {
-        if start < end {
-            let range_size = end.sub(start); //.add(1.0);
-            let rng = self.random_f64();
-            if rng.is_sign_positive() {
-                let random_index = rng % range_size;
-                start.add(random_index)
-            } else {
-                let random_index = (rng * -1.0) % range_size;
-                start.add(random_index)
-            }
-        } else if start == end {
-            start
-        } else {
-            self.random_from_f64_range(end, start)
-        }
+    pub fn release_pool(&mut self) {
+        self.entropy_pool.release_pool();
     }
 
-    /// Call with the start and end of the range (both `i32`).
-    /// The range is inclusive on both ends.
+    /// Mixes `data` into the entropy pool and regenerates it immediately.
+    ///
+    /// The data is also mixed into every pool generated afterwards.
+    /// Seeded instances stay deterministic: The same seed reseeded with the same data at the
+    /// same point will always produce the same values.
     ///
-    /// ## Example:
+    /// ## Parameters:
+    /// - `data`: The data to mix into the entropy pool.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_i32_range(-100, 100);
-    ///     println!("Chosen element {chosen_element}, in range -100, 100");
+    ///   let mut fortuna = Fortuna::new();
+    ///   fortuna.reseed(b"some fresh data");
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_from_i32_range(&mut self, start: i32, end: i32) -> i32 {
-        if start < end {
-            let range_size = end.sub(start).add(1);
-            let rng = self.random_i32();
-            if rng.is_positive() {
-                let random_index = rng % range_size;
-                start.add(random_index)
-            } else {
-                let random_index = rng.saturating_neg() % range_size;
-                start.add(random_index)
-            }
-        } else if start == end {
-            start
-        } else {
-            self.random_from_i32_range(end, start)
-        }
+    pub fn reseed(&mut self, data: &[u8]) {
+        self.entropy_pool.reseed(data);
     }
 
-    /// Call with the start and end of the range (both `i64`).
-    /// The range is inclusive on both ends.
+    /// Adds `data` to the entropy of `Fortuna` without regenerating the entropy pool.
     ///
-    /// This function needs a 64bit system for obvious reasons.
+    /// The data is absorbed with HMAC-SHA-256 into a state that keys every pool generated from the
+    /// next regeneration onwards. Use `reseed` if the data should be used immediately.
     ///
-    /// ## Example:
+    /// ## Parameters:
+    /// - `data`: The data to mix into future entropy pools.
+    ///
+    /// ## Example
     /// ```
     /// use fortuna::Fortuna;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_i64_range(-100, 100);
-    ///     println!("Chosen element {chosen_element}, in range -100, 100");
+    ///   let mut fortuna = Fortuna::new();
+    ///   fortuna.add_entropy(&42_u64.to_le_bytes());
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_from_i64_range(&mut self, start: i64, end: i64) -> i64 {
-        if start < end {
-            let range_size = end.sub(start).add(1);
-            let rng = self.random_i64();
-            if rng.is_positive() {
-                let random_index = rng % range_size;
-                start.add(random_index)
-            } else {
-                let random_index = rng.saturating_neg() % range_size;
-                start.add(random_index)
-            }
-        } else if start == end {
-            start
-        } else {
-            self.random_from_i64_range(end, start)
-        }
+    pub fn add_entropy(&mut self, data: &[u8]) {
+        self.entropy_pool.add_entropy(data);
     }
 
-    /// Call with the start and end of the range (both `isize`).
-    /// The range is inclusive on both ends.
+    /// Sets when the entropy pool regenerates itself.
     ///
-    /// ## Example:
+    /// By default the pool is regenerated once it is exhausted (`ReseedPolicy::OnExhaustion`).
+    /// Unseeded instances pull new system entropy on every regeneration.
+    ///
+    /// ## Parameters:
+    /// - `reseed_policy`: The policy to use from now on.
+    ///
+    /// ## Example
     /// ```
-    /// use fortuna::Fortuna;
+    /// use std::time::Duration;
+    ///
+    /// use fortuna::{Fortuna, ReseedPolicy};
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let chosen_element = fortuna.random_from_i_range(-100, 100);
-    ///     println!("Chosen element {chosen_element}, in range -100, 100");
+    ///   let mut fortuna = Fortuna::create_size_restricted(10_000);
+    ///   // Regenerate after 1000 bytes
+    ///   fortuna.set_reseed_policy(ReseedPolicy::EveryBytes(1_000));
+    ///   // Regenerate every minute
+    ///   fortuna.set_reseed_policy(ReseedPolicy::Every(Duration::from_secs(60)));
+    ///   // Never regenerate, cycle through the pool instead
+    ///   fortuna.set_reseed_policy(ReseedPolicy::Never);
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
     /// }
     /// ```
-    pub fn random_from_i_range(&mut self, start: isize, end: isize) -> isize {
-        if start < end {
-            let range_size = end.sub(start).add(1);
-            let rng = self.random_i32() as isize;
-            if rng.is_positive() {
-                let random_index = rng % range_size;
-                start.add(random_index)
-            } else {
-                let random_index = rng.saturating_neg() % range_size;
-                start.add(random_index)
-            }
-        } else if start == end {
-            start
-        } else {
-            self.random_from_i_range(end, start)
+    pub fn set_reseed_policy(&mut self, reseed_policy: ReseedPolicy) {
+        self.entropy_pool.set_reseed_policy(reseed_policy);
+    }
+
+    random::generation_methods!("Fortuna", "Fortuna::new()");
+}
+
+#[cfg(feature = "std")]
+impl Default for Fortuna {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `FortunaInline` is a `Fortuna` keeping its pool of `N` pseudo-random bytes inline, in an array.
+///
+/// Apart from construction, `FortunaInline` never allocates: the pool is refilled in place, so it
+/// can be used where allocating is not allowed, like real-time audio threads.
+/// Entropy is only collected on construction. Every refill expands a key into the pool with
+/// ChaCha20 and replaces the key, so earlier values can not be recovered from a captured
+/// instance. When dropped, the pool and key are overwritten with zeros.
+///
+/// The generation methods are the same as those of `Fortuna`. Seeded instances do not generate
+/// the same values as a `Fortuna` with the same seed.
+///
+/// ## Example
+/// ```
+/// use fortuna::FortunaInline;
+///
+/// fn main() {
+///   // created outside the hot path
+///   let mut fortuna = FortunaInline::<1024>::new();
+///   // no allocations from here on
+///   let random_u8: u8 = fortuna.random_u8();
+///   let random_f32: f32 = fortuna.random_from_f32_range(-1.0, 1.0);
+///   println!("Generated random u8: {}", random_u8);
+///   println!("Generated random f32: {}", random_f32);
+/// }
+/// ```
+pub struct FortunaInline<const N: usize> {
+    entropy_pool: InlinePool<N>,
+}
+
+impl<const N: usize> FortunaInline<N> {
+    /// Creates a new `FortunaInline` instance, collecting entropy from the default sources.
+    ///
+    /// `N` is the size of the pool in bytes, it has to be at least 1.
+    ///
+    /// ## Example
+    /// ```
+    /// use fortuna::FortunaInline;
+    ///
+    /// fn main() {
+    ///   let mut fortuna = FortunaInline::<4096>::new();
+    ///   let random_number: u8 = fortuna.random_u8();
+    ///   println!("Generated random u8: {}", random_number);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new() -> Self {
+        Self {
+            entropy_pool: InlinePool::new(),
         }
     }
 
-    /// Takes in the length of a collection, like a vector, and returns a valid, random, index for
-    /// it.
+    /// Creates a new `FortunaInline` instance using `callback` as its only entropy source.
+    ///
+    /// `callback` fills the given buffer with random bytes. It is only called on construction,
+    /// see `Fortuna::from_entropy_callback`.
     ///
-    /// ## Example:
+    /// ## Example
     /// ```
-    /// use fortuna::Fortuna;
+    /// use fortuna::FortunaInline;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     let collection = (0..100).collect::<Vec<usize>>();
-    ///     let random_index = fortuna.random_index(collection.len());
-    ///     println!("Chosen index {}; Number at index {}", random_index, collection[random_index]);
+    ///   let mut fortuna = FortunaInline::<256>::from_entropy_callback(|buffer| buffer.fill(42));
+    ///   println!("Generated random u8: {}", fortuna.random_u8());
     /// }
     /// ```
-    pub fn random_index(&mut self, collection_length: usize) -> usize {
-        if collection_length >= 1 {
-            self.random_with_ceiling(collection_length.saturating_sub(1))
-        } else {
-            // collection has 0 elements ... return 0 to keep no errors
-            collection_length
+    pub fn from_entropy_callback<F>(callback: F) -> Self
+    where
+        F: FnMut(&mut [u8]) + Send + Sync + 'static,
+    {
+        Self {
+            entropy_pool: InlinePool::create_with_sources(vec![Box::new(CallbackSource::new(callback))]),
         }
     }
 
-    /// Computes a random number between 0 and the `ceiling` argument.
+    /// Creates a new seeded `FortunaInline` instance.
+    /// This is useful if you want to generate the same random values every time.
+    /// The seed should be chosen randomly, and kept secret.
     ///
-    /// ## Example:
+    /// ## Parameters:
+    /// - `seed`: The seed to use for the entropy pool.
+    ///
+    /// ## Example
     /// ```
-    /// use fortuna::Fortuna;
+    /// use fortuna::FortunaInline;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     for n in 100000..200000  {
-    ///          let answ = fortuna.random_with_ceiling(n);
-    ///          println!("The random number between 0 and {} is: {}", n, answ);
-    ///     }
+    ///   let mut fortuna1 = FortunaInline::<256>::create_seeded(&[1, 2, 3, 4]);
+    ///   let mut fortuna2 = FortunaInline::<256>::create_seeded(&[1, 2, 3, 4]);
+    ///   assert_eq!(fortuna1.random_u64(), fortuna2.random_u64());
     /// }
     /// ```
-    pub fn random_with_ceiling(&mut self, ceiling: usize) -> usize {
-        self.random_from_range(usize::MIN, ceiling)
+    pub fn create_seeded(seed: &[u8]) -> Self {
+        Self {
+            entropy_pool: InlinePool::create_seeded(seed),
+        }
     }
 
-    /// Computes a random number between `usize::MAX` and the `floor` argument.
+    /// Creates a new `FortunaInline` instance seeded with a `u64`.
     ///
-    /// ## Example:
+    /// The number is mixed into a fixed size seed first, see `Fortuna::from_seed_u64`.
+    ///
+    /// ## Example
     /// ```
-    /// use fortuna::Fortuna;
+    /// use fortuna::FortunaInline;
     ///
     /// fn main() {
-    ///     let mut fortuna = Fortuna::new();
-    ///     for n in 0..100000  {
-    ///          let answ = fortuna.random_with_floor(n);
-    ///          let max_usize = usize::MAX;
-    ///          println!("The random number between {} and {} is: {}", max_usize, n, answ);
-    ///     }
+    ///   let mut fortuna = FortunaInline::<256>::from_seed_u64(42);
+    ///   println!("Generated random u8: {}", fortuna.random_u8());
     /// }
     /// ```
-    pub fn random_with_floor(&mut self, floor: usize) -> usize {
-        self.random_from_range(floor, usize::MAX)
+    pub fn from_seed_u64(seed: u64) -> Self {
+        Self::create_seeded(&seed_from_u64(seed))
     }
+
+    random::generation_methods!("FortunaInline", "FortunaInline::<256>::new()");
 }
 
-fn is_valid_ascii(input: u8) -> bool {
-    if input >= 33 && input <= 126 || input == 128 || input >= 130 && input <= 140 || input == 142 || input >= 145 && input <= 156 || input >= 158 && input <= 159 || input >= 161 && input <= 172 || input >= 174 {
-        return true;
-    } else {
-        return false;
+#[cfg(feature = "std")]
+impl<const N: usize> Default for FortunaInline<N> {
+    fn default() -> Self {
+        Self::new()
     }
 }
@@ -0,0 +1,765 @@
+//! The generation methods shared by every kind of `Fortuna`, built on a source of random bytes.
+
+use core::ops::{Add, Sub};
+
+use crate::entropy_pool::{EntropyPool, InlinePool};
+
+/// A source of pseudo-random bytes.
+pub trait RandomBytes {
+    fn random_byte(&mut self) -> u8;
+
+    /// Returns the next `L` bytes, the same bytes `L` calls to `random_byte` return.
+    fn random_bytes<const L: usize>(&mut self) -> [u8; L] {
+        core::array::from_fn(|_| self.random_byte())
+    }
+
+    /// Fills `dest` with the next bytes, the same bytes `dest.len()` calls to `random_byte` return.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest {
+            *byte = self.random_byte();
+        }
+    }
+}
+
+impl RandomBytes for EntropyPool {
+    fn random_byte(&mut self) -> u8 {
+        self.get_random_byte()
+    }
+
+    fn random_bytes<const L: usize>(&mut self) -> [u8; L] {
+        self.get_random_bytes()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        EntropyPool::fill_bytes(self, dest)
+    }
+}
+
+impl<const N: usize> RandomBytes for InlinePool<N> {
+    fn random_byte(&mut self) -> u8 {
+        self.get_random_byte()
+    }
+
+    fn random_bytes<const L: usize>(&mut self) -> [u8; L] {
+        self.get_random_bytes()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        InlinePool::fill_bytes(self, dest)
+    }
+}
+
+/// Implements the generation methods of a kind of `Fortuna` on top of its `entropy_pool`, which
+/// has to implement `RandomBytes`.
+///
+/// Used inside the `impl` block, `$name` and `$new` are the type and the constructor used in the
+/// examples.
+macro_rules! generation_methods {
+    ($name:literal, $new:literal) => {
+        /// Generates a pseudo-random `u8`.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: u8 = fortuna.random_u8();
+        ///   println!("Generated random u8: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_u8(&mut self) -> u8 {
+            $crate::random::random_u8(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `u16`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: u16 = fortuna.random_u16();
+        ///   println!("Generated random u16: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_u16(&mut self) -> u16 {
+            $crate::random::random_u16(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `u32`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: u32 = fortuna.random_u32();
+        ///   println!("Generated random u32: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_u32(&mut self) -> u32 {
+            $crate::random::random_u32(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `u64`
+        ///
+        /// This function needs a 64bit system for obvious reasons.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: u64 = fortuna.random_u64();
+        ///   println!("Generated random u64: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_u64(&mut self) -> u64 {
+            $crate::random::random_u64(&mut self.entropy_pool)
+        }
+
+        /// Fills `dest` with pseudo-random bytes.
+        ///
+        /// The bytes are copied from the entropy pool in bulk, regenerating it as needed. They are the
+        /// same bytes as calling `random_u8` for every byte of `dest`.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let mut buffer = [0u8; 1024];
+        ///   fortuna.fill_bytes(&mut buffer);
+        ///   println!("Generated random bytes: {:?}", &buffer[..8]);
+        /// }
+        /// ```
+        pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+            $crate::random::fill_bytes(&mut self.entropy_pool, dest)
+        }
+
+        /// Generates a pseudo-random `i8`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: i8 = fortuna.random_i8();
+        ///   println!("Generated random i8: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_i8(&mut self) -> i8 {
+            $crate::random::random_i8(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `i16`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: i16 = fortuna.random_i16();
+        ///   println!("Generated random i16: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_i16(&mut self) -> i16 {
+            $crate::random::random_i16(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `i32`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: i32 = fortuna.random_i32();
+        ///   println!("Generated random i32: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_i32(&mut self) -> i32 {
+            $crate::random::random_i32(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `i64`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: i64 = fortuna.random_i64();
+        ///   println!("Generated random i64: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_i64(&mut self) -> i64 {
+            $crate::random::random_i64(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `f32`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: f32 = fortuna.random_f32();
+        ///   println!("Generated random f32: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_f32(&mut self) -> f32 {
+            $crate::random::random_f32(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random `f64`
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_number: f64 = fortuna.random_f64();
+        ///   println!("Generated random f64: {}", random_number);
+        /// }
+        /// ```
+        pub fn random_f64(&mut self) -> f64 {
+            $crate::random::random_f64(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random latin character.
+        ///
+        /// ## Arguments
+        ///
+        /// * `uppercase` - `true` for upper case, `false` for lower case
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_char_uppercase: char = fortuna.random_latin_char(true);
+        ///   println!("Generated random uppercase char: {}", random_char_uppercase);
+        ///   let random_char_lowercase: char = fortuna.random_latin_char(false);
+        ///   println!("Generated random lowercase char: {}", random_char_lowercase);
+        /// }
+        /// ```
+        pub fn random_latin_char(&mut self, uppercase: bool) -> char {
+            $crate::random::random_latin_char(&mut self.entropy_pool, uppercase)
+        }
+
+        /// Generates a pseudo-random ASCII character.
+        ///
+        /// The allowed range of ASCII in HEX is 21 - FF with the exceptions 7F (DEL), 81 + 8D + 8F +
+        /// 90 + 9D (Unused), A0 (Non-breaking space) and AD (Soft hyphen)
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_char: char = fortuna.random_ascii_char();
+        ///   println!("Generated random char: {}", random_char);
+        /// }
+        /// ```
+        pub fn random_ascii_char(&mut self) -> char {
+            $crate::random::random_ascii_char(&mut self.entropy_pool)
+        }
+
+        /// Generates a pseudo-random boolean.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("   let mut fortuna = ", $new, ";")]
+        ///   let random_bool: bool = fortuna.random_bool();
+        ///   println!("Generated random bool: {}", random_bool);
+        /// }
+        /// ```
+        pub fn random_bool(&mut self) -> bool {
+            $crate::random::random_bool(&mut self.entropy_pool)
+        }
+
+        /// Call with the start and end of the range (both `usize`).
+        /// The range is inclusive on both ends.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_range(0, 100);
+        ///     println!("Chosen element {chosen_element}, in range 0-100");
+        /// }
+        /// ```
+        pub fn random_from_range(&mut self, start: usize, end: usize) -> usize {
+            $crate::random::random_from_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Call with the start and end of the range (both `u32`).
+        /// The range is inclusive on both ends.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_u32_range(0, 100);
+        ///     println!("Chosen element {chosen_element}, in range 0-100");
+        /// }
+        /// ```
+        pub fn random_from_u32_range(&mut self, start: u32, end: u32) -> u32 {
+            $crate::random::random_from_u32_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Call with the start and end of the range (both `u64`).
+        /// The range is inclusive on both ends.
+        ///
+        /// This function needs a 64bit system for obvious reasons.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_u64_range(0, 100);
+        ///     println!("Chosen element {chosen_element}, in range 0-100");
+        /// }
+        /// ```
+        pub fn random_from_u64_range(&mut self, start: u64, end: u64) -> u64 {
+            $crate::random::random_from_u64_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Call with the start and end of the range (both `f32`).
+        /// The range is inclusive on start, and never quite reaches end.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_f32_range(0.1, 100.1);
+        ///     println!("Chosen element {chosen_element}, in range 0.1-100.1");
+        /// }
+        /// ```
+        pub fn random_from_f32_range(&mut self, start: f32, end: f32) -> f32 {
+            $crate::random::random_from_f32_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Call with the start and end of the range (both `f64`).
+        /// The range is inclusive on start, and never quite reaches end.
+        ///
+        /// This function needs a 64bit system for obvious reasons.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_f64_range(-100.1, 100.1);
+        ///     println!("Chosen element {chosen_element}, in range -100.1 - 100.1");
+        /// }
+        /// ```
+        pub fn random_from_f64_range(&mut self, start: f64, end: f64) -> f64 {
+            $crate::random::random_from_f64_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Call with the start and end of the range (both `i32`).
+        /// The range is inclusive on both ends.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_i32_range(-100, 100);
+        ///     println!("Chosen element {chosen_element}, in range -100, 100");
+        /// }
+        /// ```
+        pub fn random_from_i32_range(&mut self, start: i32, end: i32) -> i32 {
+            $crate::random::random_from_i32_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Call with the start and end of the range (both `i64`).
+        /// The range is inclusive on both ends.
+        ///
+        /// This function needs a 64bit system for obvious reasons.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_i64_range(-100, 100);
+        ///     println!("Chosen element {chosen_element}, in range -100, 100");
+        /// }
+        /// ```
+        pub fn random_from_i64_range(&mut self, start: i64, end: i64) -> i64 {
+            $crate::random::random_from_i64_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Call with the start and end of the range (both `isize`).
+        /// The range is inclusive on both ends.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let chosen_element = fortuna.random_from_i_range(-100, 100);
+        ///     println!("Chosen element {chosen_element}, in range -100, 100");
+        /// }
+        /// ```
+        pub fn random_from_i_range(&mut self, start: isize, end: isize) -> isize {
+            $crate::random::random_from_i_range(&mut self.entropy_pool, start, end)
+        }
+
+        /// Takes in the length of a collection, like a vector, and returns a valid, random, index for
+        /// it.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     let collection = (0..100).collect::<Vec<usize>>();
+        ///     let random_index = fortuna.random_index(collection.len());
+        ///     println!("Chosen index {}; Number at index {}", random_index, collection[random_index]);
+        /// }
+        /// ```
+        pub fn random_index(&mut self, collection_length: usize) -> usize {
+            $crate::random::random_index(&mut self.entropy_pool, collection_length)
+        }
+
+        /// Computes a random number between 0 and the `ceiling` argument.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     for n in 100000..200000  {
+        ///          let answ = fortuna.random_with_ceiling(n);
+        ///          println!("The random number between 0 and {} is: {}", n, answ);
+        ///     }
+        /// }
+        /// ```
+        pub fn random_with_ceiling(&mut self, ceiling: usize) -> usize {
+            $crate::random::random_with_ceiling(&mut self.entropy_pool, ceiling)
+        }
+
+        /// Computes a random number between `usize::MAX` and the `floor` argument.
+        ///
+        /// ## Example:
+        /// ```
+        #[doc = concat!(" use fortuna::", $name, ";")]
+        ///
+        /// fn main() {
+        #[doc = concat!("     let mut fortuna = ", $new, ";")]
+        ///     for n in 0..100000  {
+        ///          let answ = fortuna.random_with_floor(n);
+        ///          let max_usize = usize::MAX;
+        ///          println!("The random number between {} and {} is: {}", max_usize, n, answ);
+        ///     }
+        /// }
+        /// ```
+        pub fn random_with_floor(&mut self, floor: usize) -> usize {
+            $crate::random::random_with_floor(&mut self.entropy_pool, floor)
+        }
+    };
+}
+
+pub(crate) use generation_methods;
+
+pub fn random_u8<R: RandomBytes>(bytes: &mut R) -> u8 {
+    bytes.random_byte()
+}
+
+pub fn fill_bytes<R: RandomBytes>(bytes: &mut R, dest: &mut [u8]) {
+    bytes.fill_bytes(dest)
+}
+
+pub fn random_u16<R: RandomBytes>(bytes: &mut R) -> u16 {
+    let rng = bytes.random_bytes::<2>();
+    u16::from_le_bytes(rng)
+}
+
+pub fn random_u32<R: RandomBytes>(bytes: &mut R) -> u32 {
+    let rng = bytes.random_bytes::<4>();
+    u32::from_le_bytes(rng)
+}
+
+pub fn random_u64<R: RandomBytes>(bytes: &mut R) -> u64 {
+    let rng = bytes.random_bytes::<8>();
+    u64::from_le_bytes(rng)
+}
+
+pub fn random_i8<R: RandomBytes>(bytes: &mut R) -> i8 {
+    let rng = [bytes.random_byte()];
+    i8::from_le_bytes(rng)
+}
+
+pub fn random_i16<R: RandomBytes>(bytes: &mut R) -> i16 {
+    let rng = bytes.random_bytes::<2>();
+    i16::from_le_bytes(rng)
+}
+
+pub fn random_i32<R: RandomBytes>(bytes: &mut R) -> i32 {
+    let rng = bytes.random_bytes::<4>();
+    i32::from_le_bytes(rng)
+}
+
+pub fn random_i64<R: RandomBytes>(bytes: &mut R) -> i64 {
+    let rng = bytes.random_bytes::<8>();
+    i64::from_le_bytes(rng)
+}
+
+pub fn random_f32<R: RandomBytes>(bytes: &mut R) -> f32 {
+    let rng = bytes.random_bytes::<4>();
+    let out = f32::from_le_bytes(rng);
+    if out.is_nan() {
+        random_f32(bytes)
+    } else {
+        out
+    }
+}
+
+pub fn random_f64<R: RandomBytes>(bytes: &mut R) -> f64 {
+    let rng = bytes.random_bytes::<8>();
+    let out = f64::from_le_bytes(rng);
+    if out.is_nan() {
+        random_f64(bytes)
+    } else {
+        out
+    }
+}
+
+pub fn random_latin_char<R: RandomBytes>(bytes: &mut R, uppercase: bool) -> char {
+    let chars = [
+        'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
+        'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+    ];
+    let chosen_char = chars[random_index(bytes, chars.len())];
+    if uppercase {
+        chosen_char.to_ascii_uppercase()
+    } else {
+        chosen_char
+    }
+}
+
+pub fn random_ascii_char<R: RandomBytes>(bytes: &mut R) -> char {
+    let ran_u8 = bytes.random_byte();
+    if is_valid_ascii(ran_u8) {
+        char::from_u32(ran_u8 as u32).expect("Valid ASCII character")
+    } else {
+        let try1 = ran_u8.saturating_add(bytes.random_byte());
+        if is_valid_ascii(try1) {
+            return char::from_u32(try1 as u32).expect("Valid ASCII character");
+        }
+        let try2 = ran_u8.saturating_sub(bytes.random_byte());
+        if is_valid_ascii(try2) {
+            return char::from_u32(try2 as u32).expect("Valid ASCII character");
+        }
+        let try3 = ran_u8.saturating_add(bytes.random_byte());
+        if is_valid_ascii(try3) {
+            return char::from_u32(try3 as u32).expect("Valid ASCII character");
+        }
+        let try4 = ran_u8.saturating_sub(bytes.random_byte());
+        if is_valid_ascii(try4) {
+            return char::from_u32(try4 as u32).expect("Valid ASCII character");
+        }
+        random_ascii_char(bytes)
+    }
+}
+
+pub fn random_bool<R: RandomBytes>(bytes: &mut R) -> bool {
+    let rng = bytes.random_byte();
+    !rng.is_multiple_of(2)
+}
+
+pub fn random_from_range<R: RandomBytes>(bytes: &mut R, start: usize, end: usize) -> usize {
+    if start < end {
+        let range_size = (end).saturating_sub(start).saturating_add(1);
+        let rng = random_u32(bytes);
+        let random_index = rng as usize % range_size;
+        start.saturating_add(random_index)
+    } else if start == end {
+        start
+    } else {
+        random_from_range(bytes, end, start)
+    }
+}
+
+pub fn random_from_u32_range<R: RandomBytes>(bytes: &mut R, start: u32, end: u32) -> u32 {
+    if start < end {
+        let range_size = (end).saturating_sub(start).saturating_add(1);
+        let rng = random_u32(bytes);
+        let random_index = rng % range_size;
+        start.saturating_add(random_index)
+    } else if start == end {
+        start
+    } else {
+        random_from_u32_range(bytes, end, start)
+    }
+}
+
+pub fn random_from_u64_range<R: RandomBytes>(bytes: &mut R, start: u64, end: u64) -> u64 {
+    if start < end {
+        let range_size = (end - start).saturating_add(1);
+        let rng = random_u64(bytes);
+        let random_index = rng % range_size;
+        start.saturating_add(random_index)
+    } else if start == end {
+        start
+    } else {
+        random_from_u64_range(bytes, end, start)
+    }
+}
+
+pub fn random_from_f32_range<R: RandomBytes>(bytes: &mut R, start: f32, end: f32) -> f32 {
+    if start < end {
+        // I still believe this to have an off by one error, however it is infinity small
+        // because of f32.
+        // As further reading did not help in the slightes but confirm that floating point
+        // numbers are weird I will have to live with it. It seems to grow towards end, and
+        // never reaching it. I now suspect maths shinanigans.
+        let range_size = end.sub(start); //.add(1.0);
+        let rng = random_f32(bytes);
+        if rng.is_sign_positive() {
+            let random_index = rng % range_size;
+            start.add(random_index)
+        } else {
+            let random_index = -rng % range_size;
+            start.add(random_index)
+        }
+    } else if start == end {
+        start
+    } else {
+        random_from_f32_range(bytes, end, start)
+    }
+}
+
+pub fn random_from_f64_range<R: RandomBytes>(bytes: &mut R, start: f64, end: f64) -> f64 {
+    if start < end {
+        let range_size = end.sub(start); //.add(1.0);
+        let rng = random_f64(bytes);
+        if rng.is_sign_positive() {
+            let random_index = rng % range_size;
+            start.add(random_index)
+        } else {
+            let random_index = -rng % range_size;
+            start.add(random_index)
+        }
+    } else if start == end {
+        start
+    } else {
+        random_from_f64_range(bytes, end, start)
+    }
+}
+
+pub fn random_from_i32_range<R: RandomBytes>(bytes: &mut R, start: i32, end: i32) -> i32 {
+    if start < end {
+        let range_size = end.sub(start).add(1);
+        let rng = random_i32(bytes);
+        if rng.is_positive() {
+            let random_index = rng % range_size;
+            start.add(random_index)
+        } else {
+            let random_index = rng.saturating_neg() % range_size;
+            start.add(random_index)
+        }
+    } else if start == end {
+        start
+    } else {
+        random_from_i32_range(bytes, end, start)
+    }
+}
+
+pub fn random_from_i64_range<R: RandomBytes>(bytes: &mut R, start: i64, end: i64) -> i64 {
+    if start < end {
+        let range_size = end.sub(start).add(1);
+        let rng = random_i64(bytes);
+        if rng.is_positive() {
+            let random_index = rng % range_size;
+            start.add(random_index)
+        } else {
+            let random_index = rng.saturating_neg() % range_size;
+            start.add(random_index)
+        }
+    } else if start == end {
+        start
+    } else {
+        random_from_i64_range(bytes, end, start)
+    }
+}
+
+pub fn random_from_i_range<R: RandomBytes>(bytes: &mut R, start: isize, end: isize) -> isize {
+    if start < end {
+        let range_size = end.sub(start).add(1);
+        let rng = random_i32(bytes) as isize;
+        if rng.is_positive() {
+            let random_index = rng % range_size;
+            start.add(random_index)
+        } else {
+            let random_index = rng.saturating_neg() % range_size;
+            start.add(random_index)
+        }
+    } else if start == end {
+        start
+    } else {
+        random_from_i_range(bytes, end, start)
+    }
+}
+
+pub fn random_index<R: RandomBytes>(bytes: &mut R, collection_length: usize) -> usize {
+    if collection_length >= 1 {
+        random_with_ceiling(bytes, collection_length.saturating_sub(1))
+    } else {
+        // collection has 0 elements ... return 0 to keep no errors
+        collection_length
+    }
+}
+
+pub fn random_with_ceiling<R: RandomBytes>(bytes: &mut R, ceiling: usize) -> usize {
+    random_from_range(bytes, usize::MIN, ceiling)
+}
+
+pub fn random_with_floor<R: RandomBytes>(bytes: &mut R, floor: usize) -> usize {
+    random_from_range(bytes, floor, usize::MAX)
+}
+
+fn is_valid_ascii(input: u8) -> bool {
+    (33..=126).contains(&input)
+        || input == 128
+        || (130..=140).contains(&input)
+        || input == 142
+        || (145..=156).contains(&input)
+        || (158..=159).contains(&input)
+        || (161..=172).contains(&input)
+        || input >= 174
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "std")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use fortuna::{Fortuna, FortunaInline};
+
+/// Counts the allocations of the current thread, so that tests running in parallel do not
+/// interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|allocations| allocations.get())
+}
+
+#[test]
+fn no_allocations_after_construction() {
+    let construction = allocations();
+    let mut fortuna = FortunaInline::<64>::new();
+    let mut seeded = FortunaInline::<100>::create_seeded(&[1, 2, 3]);
+    // collecting entropy allocates, so the allocator is counting
+    let before = allocations();
+    assert!(before > construction);
+    // many times the pool size, so the pools are refilled over and over
+    for _ in 0..10_000 {
+        for fortuna in [&mut fortuna as &mut dyn Generate, &mut seeded] {
+            fortuna.generate();
+        }
+    }
+    assert_eq!(allocations(), before);
+}
+
+trait Generate {
+    fn generate(&mut self);
+}
+
+impl<const N: usize> Generate for FortunaInline<N> {
+    fn generate(&mut self) {
+        self.random_u8();
+        self.random_u16();
+        self.random_u32();
+        self.random_u64();
+        self.random_i8();
+        self.random_i16();
+        self.random_i32();
+        self.random_i64();
+        self.random_f32();
+        self.random_f64();
+        self.random_latin_char(true);
+        self.random_ascii_char();
+        self.random_bool();
+        self.random_from_range(3, 17);
+        self.random_from_u32_range(3, 17);
+        self.random_from_u64_range(3, 17);
+        self.random_from_f32_range(0.5, 1.5);
+        self.random_from_f64_range(0.5, 1.5);
+        self.random_from_i32_range(-3, 17);
+        self.random_from_i64_range(-3, 17);
+        self.random_from_i_range(-3, 17);
+        self.random_index(10);
+        self.random_with_ceiling(10);
+        self.random_with_floor(10);
+        self.fill_bytes(&mut [0; 300]);
+    }
+}
+
+#[test]
+fn seeded_inline_is_deterministic() {
+    let mut fortuna1 = FortunaInline::<16>::create_seeded(&[4, 5, 6]);
+    let mut fortuna2 = FortunaInline::<16>::create_seeded(&[4, 5, 6]);
+    let mut other = FortunaInline::<16>::create_seeded(&[4, 5, 7]);
+    let mut same_as_other = 0;
+    for _ in 0..1_000 {
+        let value = fortuna1.random_u64();
+        assert_eq!(value, fortuna2.random_u64());
+        if value == other.random_u64() {
+            same_as_other += 1;
+        }
+    }
+    assert!(same_as_other < 5);
+    assert_eq!(FortunaInline::<16>::from_seed_u64(9).random_u64(), FortunaInline::<16>::from_seed_u64(9).random_u64());
+}
+
+#[test]
+fn seeded_inline_differs_from_fortuna() {
+    let seed = [4, 5, 6];
+    let mut inline = FortunaInline::<64>::create_seeded(&seed);
+    let mut fortuna = Fortuna::create_seeded(seed.to_vec());
+    let mut same = 0;
+    for _ in 0..1_000 {
+        if inline.random_u64() == fortuna.random_u64() {
+            same += 1;
+        }
+    }
+    assert_eq!(same, 0);
+}
+
+#[test]
+fn inline_completeness() {
+    let mut fortuna = FortunaInline::<1>::new();
+    let mut map: HashMap<u8, ()> = HashMap::new();
+    for _ in 0..100_000 {
+        map.insert(fortuna.random_u8(), ());
+    }
+    assert_eq!(map.len(), 256);
+}
+
+#[test]
+fn inline_word_reads_match_byte_reads() {
+    // a pool size that is no multiple of a word, so words cross the end of the pool
+    let mut words = FortunaInline::<37>::from_seed_u64(42);
+    let mut bytes = FortunaInline::<37>::from_seed_u64(42);
+    for i in 0..1_000 {
+        // misalign the words every now and then
+        if i % 7 == 0 {
+            assert_eq!(words.random_u8(), bytes.random_u8());
+        }
+        let expected: [u8; 8] = std::array::from_fn(|_| bytes.random_u8());
+        assert_eq!(words.random_u64(), u64::from_le_bytes(expected));
+    }
+}
+
+#[test]
+fn inline_fill_bytes_matches_byte_reads() {
+    let mut filled = FortunaInline::<1_000>::from_seed_u64(42);
+    let mut bytes = FortunaInline::<1_000>::from_seed_u64(42);
+    // buffers smaller and larger than the pool
+    for length in [0, 1, 7, 999, 1_000, 5_000] {
+        let mut buffer = vec![0u8; length];
+        filled.fill_bytes(&mut buffer);
+        let expected: Vec<u8> = (0..length).map(|_| bytes.random_u8()).collect();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use std::collections::HashMap;
 
 use fortuna::Fortuna;
@@ -865,11 +867,8 @@ fn seeded_completeness() {
         map.insert(fortuna.random_u8(), ());
     }
 
-    // This should Ideally be true. It is not.
-    assert_ne!(map.len(), 256);
-
-    // absolute minimum
-    assert!(map.len() > 35);
+    // Until 0.5 seeded pools only reached a fraction of the byte values, at least 36 of them
+    assert_eq!(map.len(), 256);
 }
 
 #[test]
@@ -1107,3 +1106,585 @@ fn custom_small_pool_with_1000_regenerations() {
     assert_eq!(random_ceiling_vec.len(), 10_000);
     assert_eq!(random_floor_vec.len(), 10_000);
 }
+
+#[test]
+fn regeneration_hook_is_called() {
+    use std::sync::{Arc, Mutex};
+
+    use fortuna::{RegenerationEvent, RegenerationReason};
+
+    let events: Arc<Mutex<Vec<RegenerationEvent>>> = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = events.clone();
+
+    let mut fortuna = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 1_000);
+    fortuna.on_regenerate(move |event| events_clone.lock().unwrap().push(event));
+    for _ in 0..2_500 {
+        fortuna.random_u8();
+    }
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    for (n, event) in events.iter().enumerate() {
+        assert_eq!(event.reason, RegenerationReason::Exhausted);
+        assert_eq!(event.pool_size, 1_000);
+        // iteration 0 is the initial pool
+        assert_eq!(event.seeded_iteration, Some(n as u32 + 1));
+    }
+}
+
+#[test]
+fn regeneration_hook_can_be_cleared() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let count_clone = count.clone();
+
+    let mut fortuna = Fortuna::create_size_restricted(1_000);
+    fortuna.on_regenerate(move |event| {
+        assert_eq!(event.seeded_iteration, None);
+        count_clone.fetch_add(1, Ordering::SeqCst);
+    });
+    for _ in 0..1_500 {
+        fortuna.random_u8();
+    }
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    fortuna.clear_on_regenerate();
+    for _ in 0..1_500 {
+        fortuna.random_u8();
+    }
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn reseed_is_deterministic_for_seeded() {
+    let mut seeded1 = Fortuna::create_seeded(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    let mut seeded2 = Fortuna::create_seeded(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    let mut seeded3 = Fortuna::create_seeded(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    seeded1.reseed(b"fresh data");
+    seeded2.reseed(b"fresh data");
+    seeded3.reseed(b"other data");
+    let out1: Vec<u64> = (0..100).map(|_| seeded1.random_u64()).collect();
+    let out2: Vec<u64> = (0..100).map(|_| seeded2.random_u64()).collect();
+    let out3: Vec<u64> = (0..100).map(|_| seeded3.random_u64()).collect();
+    assert_eq!(out1, out2);
+    assert_ne!(out1, out3);
+}
+
+#[test]
+fn add_entropy_applies_on_next_regeneration() {
+    let mut plain = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 1_000);
+    let mut added = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 1_000);
+    added.add_entropy(b"fresh data");
+    // the current pool is left untouched
+    let before_plain: Vec<u8> = (0..999).map(|_| plain.random_u8()).collect();
+    let before_added: Vec<u8> = (0..999).map(|_| added.random_u8()).collect();
+    assert_eq!(before_plain, before_added);
+    let after_plain: Vec<u8> = (0..999).map(|_| plain.random_u8()).collect();
+    let after_added: Vec<u8> = (0..999).map(|_| added.random_u8()).collect();
+    assert_ne!(after_plain, after_added);
+}
+
+#[test]
+fn reseed_policies() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use fortuna::{RegenerationReason, ReseedPolicy};
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let count_clone = count.clone();
+    let mut fortuna = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 1_000);
+    fortuna.set_reseed_policy(ReseedPolicy::EveryBytes(100));
+    fortuna.on_regenerate(move |event| {
+        assert_eq!(event.reason, RegenerationReason::ByteLimitReached);
+        count_clone.fetch_add(1, Ordering::SeqCst);
+    });
+    for _ in 0..1_000 {
+        fortuna.random_u8();
+    }
+    assert_eq!(count.load(Ordering::SeqCst), 9);
+
+    let mut never = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 1_000);
+    never.set_reseed_policy(ReseedPolicy::Never);
+    never.on_regenerate(|_| panic!("pool must not regenerate"));
+    let first_cycle: Vec<u8> = (0..999).map(|_| never.random_u8()).collect();
+    let second_cycle: Vec<u8> = (0..999).map(|_| never.random_u8()).collect();
+    assert_eq!(first_cycle, second_cycle);
+
+    let reasons = Arc::new(Mutex::new(Vec::new()));
+    let reasons_clone = reasons.clone();
+    let mut timed = Fortuna::create_seeded_size_restricted(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 1_000);
+    timed.set_reseed_policy(ReseedPolicy::Every(Duration::ZERO));
+    timed.on_regenerate(move |event| reasons_clone.lock().unwrap().push(event.reason));
+    timed.random_u8();
+    timed.random_u8();
+    assert_eq!(
+        *reasons.lock().unwrap(),
+        vec![RegenerationReason::IntervalElapsed, RegenerationReason::IntervalElapsed]
+    );
+}
+
+#[test]
+fn normalised_seeds_are_deterministic() {
+    let mut u64_1 = Fortuna::from_seed_u64(42);
+    let mut u64_2 = Fortuna::from_seed_u64(42);
+    let mut str_1 = Fortuna::from_seed_str("42");
+    let mut str_2 = Fortuna::from_seed_str("42");
+    let mut pass_1 = Fortuna::from_passphrase("42");
+    let mut pass_2 = Fortuna::from_passphrase("42");
+    for _ in 0..1_000 {
+        assert_eq!(u64_1.random_u64(), u64_2.random_u64());
+        assert_eq!(str_1.random_u64(), str_2.random_u64());
+        assert_eq!(pass_1.random_u64(), pass_2.random_u64());
+    }
+}
+
+#[test]
+fn normalised_small_seeds_differ() {
+    let out_0: Vec<u64> = {
+        let mut fortuna = Fortuna::from_seed_u64(0);
+        (0..100).map(|_| fortuna.random_u64()).collect()
+    };
+    let out_1: Vec<u64> = {
+        let mut fortuna = Fortuna::from_seed_u64(1);
+        (0..100).map(|_| fortuna.random_u64()).collect()
+    };
+    let out_a: Vec<u64> = {
+        let mut fortuna = Fortuna::from_seed_str("a");
+        (0..100).map(|_| fortuna.random_u64()).collect()
+    };
+    let out_b: Vec<u64> = {
+        let mut fortuna = Fortuna::from_seed_str("b");
+        (0..100).map(|_| fortuna.random_u64()).collect()
+    };
+    let out_pass_a: Vec<u64> = {
+        let mut fortuna = Fortuna::from_passphrase("a");
+        (0..100).map(|_| fortuna.random_u64()).collect()
+    };
+    assert_ne!(out_0, out_1);
+    assert_ne!(out_a, out_b);
+    assert_ne!(out_a, out_pass_a);
+}
+
+/// The variable the `env_seed_child` test seeds from.
+const ENV_SEED_VARIABLE: &str = "FORTUNA_TEST_SEED";
+
+/// Prints the first values of `Fortuna::from_env_or_entropy(ENV_SEED_VARIABLE)`.
+/// Only run by `run_env_seed_child`, which sets the variable for the child process alone.
+#[test]
+#[ignore]
+fn env_seed_child() {
+    let mut fortuna = Fortuna::from_env_or_entropy(ENV_SEED_VARIABLE);
+    let values: Vec<String> = (0..100).map(|_| fortuna.random_u64().to_string()).collect();
+    println!("values: {}", values.join(","));
+}
+
+/// Runs `env_seed_child` in a child process with `ENV_SEED_VARIABLE` set to `value`, or unset,
+/// returning the values it generated and what it printed to stderr.
+fn run_env_seed_child(value: Option<&std::ffi::OsStr>) -> (Vec<u64>, String) {
+    let mut command = std::process::Command::new(std::env::current_exe().unwrap());
+    command.args(["env_seed_child", "--exact", "--ignored", "--nocapture", "--test-threads=1"]);
+    match value {
+        Some(value) => command.env(ENV_SEED_VARIABLE, value),
+        None => command.env_remove(ENV_SEED_VARIABLE),
+    };
+    let output = command.output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stdout}{stderr}");
+    let values = stdout
+        .lines()
+        .find_map(|line| line.split_once("values: "))
+        .expect("the child prints its values")
+        .1
+        .split(',')
+        .map(|value| value.parse().unwrap())
+        .collect();
+    (values, stderr)
+}
+
+fn first_values(mut fortuna: Fortuna) -> Vec<u64> {
+    (0..100).map(|_| fortuna.random_u64()).collect()
+}
+
+#[test]
+fn env_seed_is_used() {
+    let (from_env_number, _) = run_env_seed_child(Some("42".as_ref()));
+    let (from_env_string, _) = run_env_seed_child(Some("forty two".as_ref()));
+    assert_eq!(from_env_number, first_values(Fortuna::from_seed_u64(42)));
+    assert_eq!(from_env_string, first_values(Fortuna::from_seed_str("forty two")));
+
+    // surrounding whitespace is ignored for numbers and strings alike
+    let (padded_number, _) = run_env_seed_child(Some(" 42\n".as_ref()));
+    let (padded_string, _) = run_env_seed_child(Some("  forty two\n".as_ref()));
+    assert_eq!(padded_number, from_env_number);
+    assert_eq!(padded_string, from_env_string);
+}
+
+#[test]
+fn env_seed_falls_back_to_entropy() {
+    let (values, stderr) = run_env_seed_child(None);
+    let seed: u64 = stderr
+        .lines()
+        .find_map(|line| line.split_once(&format!("{ENV_SEED_VARIABLE}=")))
+        .expect("the chosen seed is printed")
+        .1
+        .parse()
+        .unwrap();
+    assert_eq!(values, first_values(Fortuna::from_seed_u64(seed)));
+    let (replayed, _) = run_env_seed_child(Some(seed.to_string().as_ref()));
+    assert_eq!(replayed, values);
+}
+
+#[cfg(unix)]
+#[test]
+fn env_seed_not_unicode_uses_raw_bytes() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let value = std::ffi::OsStr::from_bytes(&[0x66, 0x6f, 0xff, 0x6f]);
+    let (first, stderr) = run_env_seed_child(Some(value));
+    let (second, _) = run_env_seed_child(Some(value));
+    assert!(stderr.contains("not valid unicode"), "{stderr}");
+    assert_eq!(first, second);
+}
+
+#[test]
+fn replayable_can_be_replayed() {
+    let mut fortuna = Fortuna::new_replayable();
+    let seed = fortuna.seed().expect("replayable instances are seeded").to_vec();
+    let mut replay = Fortuna::create_seeded(seed);
+    for _ in 0..10_000 {
+        assert_eq!(fortuna.random_u64(), replay.random_u64());
+    }
+}
+
+#[test]
+fn seed_is_exposed() {
+    assert_eq!(Fortuna::new().seed(), None);
+    assert_eq!(Fortuna::create_size_restricted(1_000).seed(), None);
+    assert_eq!(Fortuna::create_seeded(vec![1, 2, 3]).seed(), Some(&[1, 2, 3][..]));
+    assert_eq!(Fortuna::create_seeded_size_restricted(vec![1, 2, 3], 1_000).seed(), Some(&[1, 2, 3][..]));
+
+    let from_u64 = Fortuna::from_seed_u64(42);
+    let mut replay = Fortuna::create_seeded(from_u64.seed().unwrap().to_vec());
+    let mut from_u64 = from_u64;
+    for _ in 0..1_000 {
+        assert_eq!(from_u64.random_u64(), replay.random_u64());
+    }
+}
+
+#[test]
+fn custom_entropy_sources() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use fortuna::sources::{default_sources, EntropySource};
+
+    struct CountingSource {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl EntropySource for CountingSource {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn collect(&mut self, out: &mut Vec<u8>) {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst);
+            out.extend_from_slice(&calls.to_le_bytes());
+        }
+
+        fn estimated_entropy(&self) -> u32 {
+            0
+        }
+    }
+
+    let names: Vec<String> = default_sources().iter().map(|source| source.name().to_string()).collect();
+    assert!(names.contains(&"system time".to_string()));
+    assert!(names.contains(&"cpu features".to_string()));
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut sources = default_sources();
+    sources.retain(|source| source.name() != "file system");
+    sources.push(Box::new(CountingSource { calls: calls.clone() }));
+    let mut fortuna = Fortuna::create_size_restricted_with_sources(1_000, sources);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    for _ in 0..1_500 {
+        fortuna.random_u8();
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn no_entropy_sources() {
+    let mut fortuna = Fortuna::create_with_sources(Vec::new());
+    for _ in 0..10_000 {
+        fortuna.random_u64();
+    }
+    let mut restricted = Fortuna::create_size_restricted_with_sources(1_000, Vec::new());
+    for _ in 0..10_000 {
+        restricted.random_u64();
+    }
+}
+
+#[test]
+fn health_tests_pass_for_default_sources() {
+    let fortuna = Fortuna::try_new().expect("default sources are healthy");
+    let report = fortuna.health_report();
+    assert!(!report.is_empty());
+    assert!(report.iter().all(|health| health.passed()));
+    assert!(report.iter().any(|health| health.tested));
+    assert!(Fortuna::create_seeded(vec![1, 2, 3]).health_report().is_empty());
+}
+
+#[test]
+fn health_tests_detect_stuck_source() {
+    use fortuna::sources::{default_sources, EntropySource};
+
+    struct StuckSource;
+
+    impl EntropySource for StuckSource {
+        fn name(&self) -> &str {
+            "stuck"
+        }
+
+        fn collect(&mut self, out: &mut Vec<u8>) {
+            out.extend_from_slice(&[7; 32]);
+        }
+
+        fn estimated_entropy(&self) -> u32 {
+            256
+        }
+    }
+
+    let mut sources = default_sources();
+    sources.push(Box::new(StuckSource));
+    let err = Fortuna::try_create_with_sources(sources).err().expect("stuck source fails");
+    assert_eq!(err.failed_sources.len(), 1);
+    assert_eq!(err.failed_sources[0].name, "stuck");
+    assert!(!err.failed_sources[0].repetition_count_passed);
+    assert!(!err.failed_sources[0].adaptive_proportion_passed);
+
+    // without try, the stuck source is left out
+    let mut sources = default_sources();
+    sources.push(Box::new(StuckSource));
+    let mut fortuna = Fortuna::create_with_sources(sources);
+    let health_report = fortuna.health_report();
+    let stuck = health_report.iter().find(|health| health.name == "stuck").unwrap();
+    assert!(!stuck.passed());
+    for _ in 0..1_000 {
+        fortuna.random_u64();
+    }
+}
+
+#[test]
+fn health_tests_reject_source_stuck_across_regenerations() {
+    use fortuna::sources::EntropySource;
+
+    /// Collects the same 32 distinct bytes every time.
+    struct RepeatingSource;
+
+    impl EntropySource for RepeatingSource {
+        fn name(&self) -> &str {
+            "repeating"
+        }
+
+        fn collect(&mut self, out: &mut Vec<u8>) {
+            out.extend(0..32);
+        }
+
+        fn estimated_entropy(&self) -> u32 {
+            256
+        }
+    }
+
+    let mut fortuna = Fortuna::create_with_sources(vec![Box::new(RepeatingSource)]);
+    // a single collection looks healthy
+    assert!(fortuna.health_report()[0].passed());
+    // within the 16 collections filling a window of 512 samples, it is caught
+    let rejected = (0..16).any(|_| {
+        fortuna.reseed(b"");
+        !fortuna.health_report()[0].passed()
+    });
+    assert!(rejected);
+    let health = &fortuna.health_report()[0];
+    assert!(health.repetition_count_passed);
+    assert!(!health.adaptive_proportion_passed);
+    // rejected sources are left out, nothing else was collected
+    assert!(fortuna.entropy_report().fell_back_to_pre_generated);
+}
+
+#[test]
+fn entropy_report_lists_sources() {
+    let fortuna = Fortuna::new();
+    let report = fortuna.entropy_report();
+    assert!(!report.fell_back_to_pre_generated);
+    let names: Vec<&str> = report.sources.iter().map(|source| source.name.as_str()).collect();
+    assert!(names.contains(&"system time"));
+    assert!(names.contains(&"cpu features"));
+    for source in &report.sources {
+        assert_eq!(source.bytes, source.health.samples);
+        assert!(source.min_entropy >= 0.0);
+        assert!(source.min_entropy <= source.bytes as f64 * 8.0);
+    }
+    assert!(report.total_entropy() > 0.0);
+
+    assert!(Fortuna::create_seeded(vec![1, 2, 3]).entropy_report().sources.is_empty());
+
+    let empty = Fortuna::create_with_sources(Vec::new());
+    assert!(empty.entropy_report().fell_back_to_pre_generated);
+    assert_eq!(empty.entropy_report().total_entropy(), 0.0);
+}
+
+#[test]
+fn seeded_pool_size_does_not_depend_on_seed_length() {
+    use fortuna::DEFAULT_POOL_SIZE;
+    use std::sync::{Arc, Mutex};
+
+    for seed in [vec![1, 2], vec![7; 1_024]] {
+        let pool_sizes: Arc<Mutex<Vec<usize>>> = Arc::new(Mutex::new(Vec::new()));
+        let pool_sizes_clone = pool_sizes.clone();
+        let mut fortuna = Fortuna::create_seeded(seed.clone());
+        fortuna.on_regenerate(move |event| pool_sizes_clone.lock().unwrap().push(event.pool_size));
+        for _ in 0..DEFAULT_POOL_SIZE {
+            fortuna.random_u8();
+        }
+        assert_eq!(*pool_sizes.lock().unwrap(), vec![DEFAULT_POOL_SIZE]);
+
+        let mut same_size = Fortuna::create_seeded_size_restricted(seed.clone(), DEFAULT_POOL_SIZE);
+        let mut seeded = Fortuna::create_seeded(seed);
+        for _ in 0..100_000 {
+            assert_eq!(seeded.random_u8(), same_size.random_u8());
+        }
+    }
+}
+
+#[test]
+fn word_reads_match_byte_reads() {
+    use fortuna::ReseedPolicy;
+
+    // a pool size that is no multiple of a word, so words cross the end of the pool
+    for reseed_policy in [ReseedPolicy::OnExhaustion, ReseedPolicy::EveryBytes(13), ReseedPolicy::Never] {
+        let mut words = Fortuna::create_seeded_size_restricted(vec![4, 2], 37);
+        let mut bytes = Fortuna::create_seeded_size_restricted(vec![4, 2], 37);
+        words.set_reseed_policy(reseed_policy);
+        bytes.set_reseed_policy(reseed_policy);
+        for i in 0..1_000 {
+            // misalign the words every now and then
+            if i % 7 == 0 {
+                assert_eq!(words.random_u8(), bytes.random_u8());
+            }
+            let expected: [u8; 8] = std::array::from_fn(|_| bytes.random_u8());
+            assert_eq!(words.random_u64(), u64::from_le_bytes(expected));
+            let expected: [u8; 4] = std::array::from_fn(|_| bytes.random_u8());
+            assert_eq!(words.random_u32(), u32::from_le_bytes(expected));
+        }
+    }
+}
+
+#[test]
+fn fill_bytes_matches_byte_reads() {
+    use fortuna::ReseedPolicy;
+
+    for reseed_policy in [ReseedPolicy::OnExhaustion, ReseedPolicy::EveryBytes(13), ReseedPolicy::Never] {
+        let mut filled = Fortuna::create_seeded_size_restricted(vec![4, 2], 1_000);
+        let mut bytes = Fortuna::create_seeded_size_restricted(vec![4, 2], 1_000);
+        filled.set_reseed_policy(reseed_policy);
+        bytes.set_reseed_policy(reseed_policy);
+        // buffers smaller and larger than the pool
+        for length in [0, 1, 7, 999, 1_000, 5_000] {
+            let mut buffer = vec![0u8; length];
+            filled.fill_bytes(&mut buffer);
+            let expected: Vec<u8> = (0..length).map(|_| bytes.random_u8()).collect();
+            assert_eq!(buffer, expected);
+        }
+    }
+}
+
+#[test]
+fn lazy_construction_defers_generation() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use fortuna::sources::{EntropySource, SystemTimeSource};
+
+    struct CountingSource {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl EntropySource for CountingSource {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn collect(&mut self, out: &mut Vec<u8>) {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            out.extend_from_slice(&[1, 2, 3, 4]);
+        }
+
+        fn estimated_entropy(&self) -> u32 {
+            0
+        }
+    }
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut warmed_up = Fortuna::create_lazy_with_sources(vec![
+        Box::new(SystemTimeSource),
+        Box::new(CountingSource { calls: calls.clone() }),
+    ]);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = events.clone();
+    warmed_up.on_regenerate(move |event| events_clone.lock().unwrap().push(event));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(warmed_up.entropy_report().sources.is_empty());
+
+    warmed_up.warm_up();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(warmed_up.entropy_report().sources.len(), 2);
+    warmed_up.warm_up();
+    warmed_up.random_u64();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    // generating the initial pool is no regeneration
+    assert!(events.lock().unwrap().is_empty());
+
+    // the first request generates the pool without `warm_up`
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut on_first_use = Fortuna::create_lazy_with_sources(vec![Box::new(CountingSource { calls: calls.clone() })]);
+    let mut buffer = [0u8; 100];
+    on_first_use.fill_bytes(&mut buffer);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(buffer.iter().any(|byte| *byte != 0));
+
+    let mut lazy = Fortuna::new_lazy();
+    let mut values: Vec<u64> = (0..1_000).map(|_| lazy.random_u64()).collect();
+    values.sort_unstable();
+    values.dedup();
+    assert_eq!(values.len(), 1_000);
+}
+
+#[test]
+fn release_pool_regenerates_on_next_use() {
+    use fortuna::RegenerationReason;
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = events.clone();
+    let mut fortuna = Fortuna::create_size_restricted(1_000);
+    fortuna.on_regenerate(move |event| events_clone.lock().unwrap().push(event));
+    fortuna.set_zeroize_consumed(true);
+    let before = fortuna.random_u64();
+    fortuna.release_pool();
+    // releasing twice is fine
+    fortuna.release_pool();
+    assert!(events.lock().unwrap().is_empty());
+
+    fortuna.warm_up();
+    assert_ne!(fortuna.random_u64(), before);
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].reason, RegenerationReason::Released);
+    assert_eq!(events[0].pool_size, 1_000);
+}
@@ -0,0 +1,63 @@
+//! Only uses what is available without the `std` feature.
+
+use core::time::Duration;
+
+use fortuna::{Fortuna, FortunaInline, ReseedPolicy};
+
+/// A stand-in for a hardware random number generator.
+fn counter_entropy() -> impl FnMut(&mut [u8]) + Send + Sync + 'static {
+    let mut counter: u8 = 0;
+    move |buffer: &mut [u8]| {
+        for byte in buffer {
+            counter = counter.wrapping_add(1);
+            *byte = counter;
+        }
+    }
+}
+
+#[test]
+fn seeded_without_std() {
+    let mut fortuna1 = Fortuna::create_seeded_size_restricted(vec![1, 2, 3], 100);
+    let mut fortuna2 = Fortuna::from_seed_str("seed");
+    let mut fortuna3 = Fortuna::from_seed_str("seed");
+    for _ in 0..1_000 {
+        fortuna1.random_from_range(0, 10);
+        assert_eq!(fortuna2.random_f64(), fortuna3.random_f64());
+    }
+}
+
+#[test]
+fn entropy_callback() {
+    let mut fortuna1 = Fortuna::from_entropy_callback(counter_entropy());
+    let mut fortuna2 = Fortuna::from_entropy_callback(counter_entropy());
+    let report = fortuna1.entropy_report();
+    assert_eq!(report.sources.len(), 1);
+    assert_eq!(report.sources[0].name, "callback");
+    assert_eq!(report.sources[0].bytes, 32);
+    // without `std` no time is measured, so the same entropy gives the same values
+    if !cfg!(feature = "std") {
+        assert_eq!(fortuna1.random_u64(), fortuna2.random_u64());
+    }
+    fortuna2.random_u64();
+    for _ in 0..100_000 {
+        fortuna1.random_u8();
+    }
+
+    let mut inline = FortunaInline::<64>::from_entropy_callback(counter_entropy());
+    for _ in 0..1_000 {
+        inline.random_bool();
+    }
+}
+
+#[test]
+fn every_without_clock() {
+    let mut fortuna1 = Fortuna::from_seed_str("seed");
+    let mut fortuna2 = Fortuna::from_seed_str("seed");
+    fortuna1.set_reseed_policy(ReseedPolicy::Every(Duration::ZERO));
+    // without a clock no time elapses, so the pool is only regenerated once it is exhausted
+    if !cfg!(feature = "std") {
+        for _ in 0..10_000 {
+            assert_eq!(fortuna1.random_u8(), fortuna2.random_u8());
+        }
+    }
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use fortuna::Fortuna;
+
+const TARGET: &str = "wasm32-unknown-unknown";
+
+/// Runs `examples/wasm_smoke.rs` and prints its results, one per line.
+/// WebAssembly has no unsigned integers, a returned `u64` arrives as signed `BigInt`.
+const NODE_SCRIPT: &str = r#"
+const fs = require("fs");
+const crypto = require("crypto");
+WebAssembly.instantiate(fs.readFileSync(process.argv[1])).then(({ instance }) => {
+    const wasm = instance.exports;
+    new Uint8Array(wasm.memory.buffer, wasm.host_entropy(), 32).set(crypto.randomBytes(32));
+    console.log(BigInt.asUintN(64, wasm.run()).toString());
+    console.log(BigInt.asUintN(64, wasm.seeded_u64(42n)).toString());
+});
+"#;
+
+/// Returns why the test can not run, if the wasm32 target or Node.js is missing.
+fn missing_tools() -> Option<&'static str> {
+    let target_libdir = Command::new("rustc")
+        .args(["--print", "target-libdir", "--target", TARGET])
+        .output()
+        .ok()
+        .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()));
+    if !target_libdir.is_some_and(|libdir| libdir.is_dir()) {
+        return Some("the wasm32-unknown-unknown target is not installed");
+    }
+    if Command::new("node").arg("--version").output().is_err() {
+        return Some("Node.js is not installed");
+    }
+    None
+}
+
+#[test]
+#[ignore = "needs Node.js and the wasm32-unknown-unknown target, run with `cargo test -- --ignored`"]
+fn runs_on_wasm32() {
+    if let Some(reason) = missing_tools() {
+        panic!("can not run the wasm32 test: {reason}");
+    }
+    // a separate target directory, the one of this test run is locked
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("wasm");
+    let build = Command::new(env!("CARGO"))
+        .args(["build", "--release", "--example", "wasm_smoke", "--target", TARGET])
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert!(build.status.success(), "{}", String::from_utf8_lossy(&build.stderr));
+
+    let module = target_dir.join(TARGET).join("release/examples/wasm_smoke.wasm");
+    let run = Command::new("node").arg("-e").arg(NODE_SCRIPT).arg(&module).output().unwrap();
+    // a panic traps, making node fail
+    assert!(run.status.success(), "{}", String::from_utf8_lossy(&run.stderr));
+    let stdout = String::from_utf8(run.stdout).unwrap();
+    let results: Vec<u64> = stdout.lines().map(|line| line.parse().unwrap()).collect();
+    assert_eq!(results.len(), 2);
+    assert_ne!(results[0], 0);
+    // seeded instances generate the same values on every target
+    assert_eq!(results[1], Fortuna::from_seed_u64(42).random_u64());
+}